@@ -0,0 +1,272 @@
+use crate::error::TruthlinkedError;
+use serde::{Deserialize, Serialize};
+use serde_json::Value;
+use std::fmt;
+use std::str::FromStr;
+
+/// Source format of IAM logs replayed through shadow mode
+///
+/// Each adapter has a local parser that checks records carry the fields the
+/// server needs and normalizes them to compact single-line JSON, so malformed
+/// input is reported before it is uploaded.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::IamAdapter;
+///
+/// let parsed = IamAdapter::Okta.parse_lines([
+///     r#"{"uuid":"1","published":"2024-01-01T00:00:00Z","eventType":"user.session.start","actor":{"id":"u1"}}"#,
+///     "not json",
+/// ]);
+/// assert_eq!(parsed.records.len(), 1);
+/// assert_eq!(parsed.malformed[0].line, 2);
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum IamAdapter {
+    /// AWS CloudTrail events, one per line or as a `{"Records": [...]}` file
+    #[serde(rename = "aws-cloudtrail")]
+    AwsCloudTrail,
+    /// Azure AD (Entra ID) sign-in and audit logs, one per line or as a
+    /// Microsoft Graph `{"value": [...]}` page
+    #[serde(rename = "azure-ad")]
+    AzureAd,
+    /// Okta System Log events
+    #[serde(rename = "okta")]
+    Okta,
+    /// GCP Cloud Audit Logs entries
+    #[serde(rename = "gcp-audit")]
+    GcpAudit,
+}
+
+impl IamAdapter {
+    /// All adapters
+    pub const ALL: [IamAdapter; 4] = [
+        IamAdapter::AwsCloudTrail,
+        IamAdapter::AzureAd,
+        IamAdapter::Okta,
+        IamAdapter::GcpAudit,
+    ];
+    
+    /// Name the replay endpoint expects
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::AwsCloudTrail => "aws-cloudtrail",
+            Self::AzureAd => "azure-ad",
+            Self::Okta => "okta",
+            Self::GcpAudit => "gcp-audit",
+        }
+    }
+    
+    /// Validate and normalize one input line
+    ///
+    /// Returns the records on the line as compact JSON; container formats
+    /// (CloudTrail `Records`, Graph `value`) yield several. The error
+    /// describes what is wrong with the line.
+    pub fn parse_line(&self, line: &str) -> Result<Vec<String>, String> {
+        let value: Value = serde_json::from_str(line.trim()).map_err(|e| format!("invalid JSON: {}", e))?;
+        
+        let records = match (self.container_key(), &value) {
+            (Some(key), Value::Object(object)) if object.contains_key(key) => match &object[key] {
+                Value::Array(records) => records.iter().collect(),
+                _ => return Err(format!("\"{}\" is not an array", key)),
+            },
+            _ => vec![&value],
+        };
+        
+        records
+            .into_iter()
+            .map(|record| {
+                self.check_record(record)?;
+                serde_json::to_string(record).map_err(|e| e.to_string())
+            })
+            .collect()
+    }
+    
+    /// Validate and normalize `lines`, collecting malformed ones
+    ///
+    /// Blank lines are skipped. Line numbers in the report start at 1.
+    pub fn parse_lines<I>(&self, lines: I) -> ParsedLogs
+    where
+        I: IntoIterator,
+        I::Item: AsRef<str>,
+    {
+        let mut parsed = ParsedLogs::default();
+        for (index, line) in lines.into_iter().enumerate() {
+            let line = line.as_ref();
+            if line.trim().is_empty() {
+                continue;
+            }
+            match self.parse_line(line) {
+                Ok(records) => parsed.records.extend(records),
+                Err(reason) => parsed.malformed.push(MalformedLine {
+                    line: index as u64 + 1,
+                    reason,
+                }),
+            }
+        }
+        parsed
+    }
+    
+    /// Key of the array wrapping records in bulk exports
+    fn container_key(&self) -> Option<&'static str> {
+        match self {
+            Self::AwsCloudTrail => Some("Records"),
+            Self::AzureAd => Some("value"),
+            _ => None,
+        }
+    }
+    
+    /// Check that `record` has the fields this source always emits
+    fn check_record(&self, record: &Value) -> Result<(), String> {
+        if !record.is_object() {
+            return Err("record is not a JSON object".to_string());
+        }
+        
+        match self {
+            Self::AwsCloudTrail => {
+                require_str(record, &["eventTime", "eventName", "eventSource"])?;
+                require_object(record, "userIdentity")
+            }
+            Self::AzureAd => {
+                require_str(record, &["id"])?;
+                // Sign-in logs carry createdDateTime, audit logs activityDateTime
+                if record.get("createdDateTime").is_some_and(Value::is_string)
+                    || record.get("activityDateTime").is_some_and(Value::is_string)
+                {
+                    Ok(())
+                } else {
+                    Err("missing \"createdDateTime\" or \"activityDateTime\"".to_string())
+                }
+            }
+            Self::Okta => {
+                require_str(record, &["uuid", "published", "eventType"])?;
+                require_object(record, "actor")
+            }
+            Self::GcpAudit => {
+                require_str(record, &["timestamp"])?;
+                require_object(record, "protoPayload")?;
+                require_str(&record["protoPayload"], &["methodName"])
+                    .map_err(|reason| format!("protoPayload: {}", reason))
+            }
+        }
+    }
+}
+
+fn require_str(record: &Value, fields: &[&str]) -> Result<(), String> {
+    match fields.iter().find(|field| !record.get(**field).is_some_and(Value::is_string)) {
+        Some(field) => Err(format!("missing \"{}\"", field)),
+        None => Ok(()),
+    }
+}
+
+fn require_object(record: &Value, field: &str) -> Result<(), String> {
+    if record.get(field).is_some_and(Value::is_object) {
+        Ok(())
+    } else {
+        Err(format!("missing \"{}\"", field))
+    }
+}
+
+impl fmt::Display for IamAdapter {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for IamAdapter {
+    type Err = TruthlinkedError;
+    
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|adapter| adapter.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| TruthlinkedError::InvalidRequest(format!("Unknown IAM adapter: {}", name)))
+    }
+}
+
+impl From<IamAdapter> for String {
+    fn from(adapter: IamAdapter) -> Self {
+        adapter.as_str().to_string()
+    }
+}
+
+/// An input line that failed local validation
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct MalformedLine {
+    /// 1-based line number in the input
+    pub line: u64,
+    /// What is wrong with it
+    pub reason: String,
+}
+
+impl fmt::Display for MalformedLine {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.reason)
+    }
+}
+
+/// Result of [`IamAdapter::parse_lines`]
+#[derive(Debug, Clone, Default)]
+pub struct ParsedLogs {
+    /// Normalized records, ready for replay
+    pub records: Vec<String>,
+    /// Lines that were rejected
+    pub malformed: Vec<MalformedLine>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_names_round_trip() {
+        for adapter in IamAdapter::ALL {
+            assert_eq!(adapter.as_str().parse::<IamAdapter>().unwrap(), adapter);
+            assert_eq!(serde_json::to_string(&adapter).unwrap(), format!("\"{}\"", adapter));
+        }
+        assert_eq!("Okta".parse::<IamAdapter>().unwrap(), IamAdapter::Okta);
+        assert_eq!("AWS-CloudTrail".parse::<IamAdapter>().unwrap(), IamAdapter::AwsCloudTrail);
+        assert!("splunk".parse::<IamAdapter>().is_err());
+    }
+    
+    #[test]
+    fn test_cloudtrail_records_file_is_split() {
+        let line = r#"{"Records": [
+            {"eventTime": "2024-01-01T00:00:00Z", "eventName": "GetObject", "eventSource": "s3.amazonaws.com", "userIdentity": {}},
+            {"eventTime": "2024-01-01T00:00:01Z", "eventName": "PutObject", "eventSource": "s3.amazonaws.com", "userIdentity": {}}
+        ]}"#;
+        
+        let records = IamAdapter::AwsCloudTrail.parse_line(&line.replace('\n', " ")).unwrap();
+        assert_eq!(records.len(), 2);
+        assert!(records[0].starts_with('{') && !records[0].contains('\n'));
+    }
+    
+    #[test]
+    fn test_missing_fields_reported() {
+        assert_eq!(
+            IamAdapter::GcpAudit.parse_line(r#"{"timestamp": "t", "protoPayload": {}}"#),
+            Err("protoPayload: missing \"methodName\"".to_string())
+        );
+        assert_eq!(
+            IamAdapter::AzureAd.parse_line(r#"{"id": "1"}"#),
+            Err("missing \"createdDateTime\" or \"activityDateTime\"".to_string())
+        );
+        assert!(IamAdapter::AzureAd.parse_line(r#"{"id": "1", "activityDateTime": "t"}"#).is_ok());
+        assert!(IamAdapter::Okta.parse_line("[]").is_err());
+    }
+    
+    #[test]
+    fn test_parse_lines_reports_line_numbers() {
+        let parsed = IamAdapter::Okta.parse_lines([
+            r#"{"actor": {"id": "u1"}, "eventType": "e", "published": "p", "uuid": "1"}"#,
+            "",
+            r#"{"actor": {}, "published": "p", "uuid": "1"}"#,
+            "{",
+        ]);
+        
+        assert_eq!(parsed.records, vec![r#"{"actor":{"id":"u1"},"eventType":"e","published":"p","uuid":"1"}"#]);
+        let lines: Vec<u64> = parsed.malformed.iter().map(|m| m.line).collect();
+        assert_eq!(lines, vec![3, 4]);
+        assert_eq!(parsed.malformed[0].to_string(), "line 3: missing \"eventType\"");
+    }
+}
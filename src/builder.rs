@@ -1,7 +1,10 @@
 use crate::error::{Result, TruthlinkedError};
 use crate::logging::LoggingConfig;
-use crate::retry::RetryConfig;
+use crate::retry::{RetryConfig, RetryExecutor, RetryPolicy};
+use crate::tls::{ClientIdentity, TlsSettings};
+use crate::witness::WitnessKeyring;
 use reqwest::header::{HeaderMap, HeaderName, HeaderValue};
+use std::sync::Arc;
 use std::time::Duration;
 
 /// Builder for configuring Truthlinked API client
@@ -32,6 +35,9 @@ pub struct ClientBuilder {
     timeout: Duration,
     connect_timeout: Duration,
     retry_config: RetryConfig,
+    retry_policy: Option<Arc<dyn RetryPolicy>>,
+    endpoint_retry_policies: Vec<(String, Arc<dyn RetryPolicy>)>,
+    honor_retry_after: bool,
     logging_config: LoggingConfig,
     custom_headers: HeaderMap,
     user_agent: Option<String>,
@@ -40,7 +46,8 @@ pub struct ClientBuilder {
     pool_idle_timeout: Duration,
     enable_gzip: bool,
     enable_brotli: bool,
-    certificate_pins: Vec<String>,
+    tls: TlsSettings,
+    witness_keyring: Option<WitnessKeyring>,
     allow_http: bool,  // For testing only
 }
 
@@ -57,6 +64,9 @@ impl ClientBuilder {
             timeout: Duration::from_secs(30),
             connect_timeout: Duration::from_secs(10),
             retry_config: RetryConfig::production(),
+            retry_policy: None,
+            endpoint_retry_policies: Vec::new(),
+            honor_retry_after: false,
             logging_config: LoggingConfig::production(),
             custom_headers: HeaderMap::new(),
             user_agent: None,
@@ -65,7 +75,8 @@ impl ClientBuilder {
             pool_idle_timeout: Duration::from_secs(90),
             enable_gzip: true,
             enable_brotli: true,
-            certificate_pins: Vec::new(),
+            tls: TlsSettings::default(),
+            witness_keyring: None,
             allow_http: false,
         }
     }
@@ -83,17 +94,68 @@ impl ClientBuilder {
     }
     
     /// Set retry configuration
+    /// 
+    /// Ignored once a [`retry_policy`](Self::retry_policy) is set, whatever
+    /// the call order.
     pub fn retry_config(mut self, config: RetryConfig) -> Self {
         self.retry_config = config;
         self
     }
     
     /// Set number of retry attempts (convenience method)
+    /// 
+    /// Changes the retry configuration only, so it has no effect once a
+    /// [`retry_policy`](Self::retry_policy) is set; use the policy's own
+    /// attempt limit instead.
     pub fn retries(mut self, max_attempts: u32) -> Self {
         self.retry_config.max_attempts = max_attempts;
         self
     }
     
+    /// Use a custom retry policy instead of the retry configuration
+    /// 
+    /// The policy takes precedence: [`retry_config`](Self::retry_config) and
+    /// [`retries`](Self::retries) no longer apply, before or after this call.
+    /// 
+    /// # Example
+    /// ```rust,no_run
+    /// use truthlinked_sdk::{ClientBuilder, DecorrelatedJitter, RetryPolicy};
+    /// use std::time::Duration;
+    /// 
+    /// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// let policy = DecorrelatedJitter::new(Duration::from_millis(100), Duration::from_secs(5))
+    ///     .max_attempts(6)
+    ///     .with_deadline(Duration::from_secs(20));
+    /// 
+    /// let client = ClientBuilder::new("https://api.truthlinked.org", "tl_free_...")
+    ///     .retry_policy(policy)
+    ///     .build()?;
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn retry_policy(mut self, policy: impl RetryPolicy + 'static) -> Self {
+        self.retry_policy = Some(Arc::new(policy));
+        self
+    }
+    
+    /// Use `policy` for requests to API paths starting with `path`
+    /// 
+    /// The most specific matching path wins, e.g. `"/witness/submit"` to
+    /// change how witness submissions are retried.
+    pub fn endpoint_retry_policy(mut self, path: impl Into<String>, policy: impl RetryPolicy + 'static) -> Self {
+        self.endpoint_retry_policies.push((path.into(), Arc::new(policy)));
+        self
+    }
+    
+    /// Retry 429 responses, waiting for the server's `Retry-After` interval
+    /// 
+    /// Advised delays are capped at the retry config's `max_delay`, or for a
+    /// custom [`retry_policy`](Self::retry_policy) by the policy and at 30s.
+    pub fn honor_retry_after(mut self, enable: bool) -> Self {
+        self.honor_retry_after = enable;
+        self
+    }
+    
     /// Set logging configuration
     pub fn logging_config(mut self, config: LoggingConfig) -> Self {
         self.logging_config = config;
@@ -156,10 +218,50 @@ impl ClientBuilder {
     
     /// Add certificate pin for enhanced security
     /// 
+    /// The server's chain is validated as usual, then at least one of its
+    /// certificates must have a pinned public key; otherwise requests fail
+    /// with [`TruthlinkedError::CertificatePinMismatch`]. Call this more than
+    /// once to add backup pins, e.g. for the next key or the issuing CA.
+    /// 
     /// # Arguments
-    /// * `pin` - SHA256 hash of the certificate's public key (base64 encoded)
+    /// * `pin` - SHA256 hash of the certificate's public key (base64 encoded,
+    ///   optionally prefixed with `sha256/`)
     pub fn certificate_pin(mut self, pin: impl Into<String>) -> Self {
-        self.certificate_pins.push(pin.into());
+        self.tls.pins.push(pin.into());
+        self
+    }
+    
+    /// Trust the CA certificate(s) in a PEM bundle, e.g. an on-prem private CA
+    pub fn add_root_certificate(mut self, pem: impl AsRef<[u8]>) -> Result<Self> {
+        let certs = crate::tls::parse_certificates(pem.as_ref())?;
+        self.tls.root_certificates.extend(certs);
+        Ok(self)
+    }
+    
+    /// Enable/disable the built-in public root certificates (default: enabled)
+    /// 
+    /// Disable to trust only the roots added with `add_root_certificate`.
+    pub fn tls_built_in_roots(mut self, enable: bool) -> Self {
+        self.tls.built_in_roots = enable;
+        self
+    }
+    
+    /// Present a client certificate for mutual TLS
+    /// 
+    /// # Arguments
+    /// * `cert_pem` - Client certificate, followed by any intermediates
+    /// * `key_pem` - Private key (PKCS#8, PKCS#1 or SEC1), zeroized on drop
+    pub fn client_identity(mut self, cert_pem: impl AsRef<[u8]>, key_pem: impl AsRef<[u8]>) -> Result<Self> {
+        self.tls.identity = Some(ClientIdentity::from_pem(cert_pem.as_ref(), key_pem.as_ref())?);
+        Ok(self)
+    }
+    
+    /// Verify witness tree heads against a keyring
+    /// 
+    /// When set, `get_latest_sth` and `get_sth` refuse any signed tree head
+    /// whose signature does not verify against a trusted key.
+    pub fn witness_keyring(mut self, keyring: WitnessKeyring) -> Self {
+        self.witness_keyring = Some(keyring);
         self
     }
     
@@ -198,20 +300,36 @@ impl ClientBuilder {
             client_builder = client_builder.proxy(proxy);
         }
         
-        // TODO: Implement certificate pinning when reqwest supports it
-        if !self.certificate_pins.is_empty() {
-            tracing::warn!("Certificate pinning not yet implemented in reqwest");
+        // Pins, custom roots and client certificates need our own rustls config
+        if !self.tls.is_default() {
+            client_builder = client_builder.use_preconfigured_tls(self.tls.client_config()?);
         }
         
         let http_client = client_builder.build()
             .map_err(|_| TruthlinkedError::InvalidRequest("Failed to build HTTP client".to_string()))?;
         
+        let honor_retry_after = self.honor_retry_after;
+        let executor = |policy: Arc<dyn RetryPolicy>| {
+            RetryExecutor::from_shared(policy).honor_retry_after(honor_retry_after)
+        };
+        let retry_executor = match self.retry_policy {
+            Some(policy) => executor(policy),
+            None => executor(Arc::new(self.retry_config.clone())).max_delay(self.retry_config.max_delay),
+        };
+        let endpoint_executors = self
+            .endpoint_retry_policies
+            .into_iter()
+            .map(|(path, policy)| (path, executor(policy)))
+            .collect();
+        
         crate::client::Client::with_config(
             http_client,
             self.base_url,
             self.license_key,
-            self.retry_config,
+            retry_executor,
+            endpoint_executors,
             self.logging_config,
+            self.witness_keyring,
         )
     }
 }
@@ -283,4 +401,23 @@ mod tests {
         let test_builder = ClientBuilder::testing("https://api.example.com", "key");
         assert_eq!(test_builder.timeout, Duration::from_secs(5));
     }
+    
+    #[test]
+    fn test_invalid_certificate_pin_rejected() {
+        let result = ClientBuilder::new("https://api.example.com", "key")
+            .certificate_pin("not-a-pin")
+            .build();
+        
+        assert!(matches!(result, Err(TruthlinkedError::InvalidRequest(_))));
+    }
+    
+    #[test]
+    fn test_no_trusted_roots_rejected() {
+        let result = ClientBuilder::new("https://api.example.com", "key")
+            .tls_built_in_roots(false)
+            .build();
+        
+        assert!(matches!(result, Err(TruthlinkedError::InvalidRequest(_))));
+        assert!(ClientBuilder::new("https://api.example.com", "key").add_root_certificate("not pem").is_err());
+    }
 }
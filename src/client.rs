@@ -1,11 +1,96 @@
-use crate::error::{Result, TruthlinkedError};
+use crate::compliance::{ComplianceFramework, ComplianceQuery, ComplianceReport};
+use crate::error::{RateLimitInfo, Result, TruthlinkedError};
+use crate::idempotency::IdempotencyKey;
 use crate::license::LicenseKey;
 use crate::logging::{LoggingConfig, RequestLogger, RequestTimer};
+use crate::pagination::{Page, PageFuture, PageStream};
+use crate::query::{AuditLogQuery, ShadowDecisionQuery};
+use crate::replay::{Chunk, Chunker, ReplayProgress, ReplayUpload};
+use crate::shadow::ShadowPolicy;
 use crate::retry::{RetryConfig, RetryExecutor};
 use crate::signing::RequestSigner;
 use crate::types::*;
-use reqwest::{Client as HttpClient, StatusCode};
+use crate::witness::WitnessKeyring;
+use reqwest::header::HeaderMap;
+use reqwest::{Client as HttpClient, Method, StatusCode};
+use serde::de::DeserializeOwned;
+use serde::Serialize;
 use std::time::Duration;
+use tokio::io::{AsyncBufReadExt, AsyncRead, BufReader};
+
+/// A single API call, described once and replayed on every retry attempt
+///
+/// The body is serialized up front so that every attempt signs and sends
+/// exactly the same bytes.
+struct ApiRequest {
+    method: Method,
+    path: String,
+    query: Vec<(&'static str, String)>,
+    body: Vec<u8>,
+    content_encoding: Option<&'static str>,
+    idempotency_key: Option<IdempotencyKey>,
+    authenticated: bool,
+}
+
+impl ApiRequest {
+    fn get(path: impl Into<String>) -> Self {
+        Self {
+            method: Method::GET,
+            path: path.into(),
+            query: Vec::new(),
+            body: Vec::new(),
+            content_encoding: None,
+            idempotency_key: None,
+            authenticated: true,
+        }
+    }
+    
+    fn post<B: Serialize>(path: impl Into<String>, body: &B) -> Result<Self> {
+        Ok(Self {
+            method: Method::POST,
+            path: path.into(),
+            query: Vec::new(),
+            body: serde_json::to_vec(body)?,
+            content_encoding: None,
+            idempotency_key: None,
+            authenticated: true,
+        })
+    }
+    
+    fn query(mut self, name: &'static str, value: impl ToString) -> Self {
+        self.query.push((name, value.to_string()));
+        self
+    }
+    
+    /// Compress the body with gzip
+    #[cfg(feature = "gzip")]
+    fn gzip(mut self) -> Result<Self> {
+        use std::io::Write;
+        
+        let mut encoder = flate2::write::GzEncoder::new(Vec::new(), flate2::Compression::default());
+        encoder
+            .write_all(&self.body)
+            .and_then(|_| encoder.finish())
+            .map(|body| {
+                self.body = body;
+                self.content_encoding = Some("gzip");
+                self
+            })
+            .map_err(|e| TruthlinkedError::InvalidRequest(format!("Failed to compress body: {}", e)))
+    }
+    
+    /// Send `key` as `Idempotency-Key` so a retried attempt is not processed twice
+    fn idempotency_key(mut self, key: IdempotencyKey) -> Self {
+        self.idempotency_key = Some(key);
+        self
+    }
+    
+    /// Send the request without the license key (public endpoints only)
+    fn unauthenticated(mut self) -> Self {
+        self.authenticated = false;
+        self
+    }
+}
 
 /// Truthlinked Authority Fabric API client
 ///
@@ -51,8 +136,12 @@ pub struct Client {
     signer: RequestSigner,
     /// Retry executor with exponential backoff
     retry_executor: RetryExecutor,
+    /// Executors for API paths with their own retry policy, by path prefix
+    endpoint_retry_executors: Vec<(String, RetryExecutor)>,
     /// Request/response logger with credential redaction
     logger: RequestLogger,
+    /// Trusted witness keys; when set, unverifiable tree heads are refused
+    witness_keyring: Option<WitnessKeyring>,
 }
 
 impl Client {
@@ -112,7 +201,9 @@ impl Client {
             base_url: base_url_string,
             license_key: LicenseKey::new(license_key_string),
             retry_executor: RetryExecutor::new(RetryConfig::production()),
+            endpoint_retry_executors: Vec::new(),
             logger: RequestLogger::new(LoggingConfig::production()),
+            witness_keyring: None,
         })
     }
     
@@ -121,16 +212,20 @@ impl Client {
         http_client: HttpClient,
         base_url: String,
         license_key: String,
-        retry_config: RetryConfig,
+        retry_executor: RetryExecutor,
+        endpoint_retry_executors: Vec<(String, RetryExecutor)>,
         logging_config: LoggingConfig,
+        witness_keyring: Option<WitnessKeyring>,
     ) -> Result<Self> {
         Ok(Self {
             http_client,
             base_url,
             signer: RequestSigner::new(&license_key),
             license_key: LicenseKey::new(license_key),
-            retry_executor: RetryExecutor::new(retry_config),
+            retry_executor,
+            endpoint_retry_executors,
             logger: RequestLogger::new(logging_config),
+            witness_keyring,
         })
     }
     
@@ -155,53 +250,9 @@ impl Client {
     /// # }
     /// ```
     pub async fn health(&self) -> Result<HealthResponse> {
-        let url = format!("{}/health", self.base_url);
-        
-        self.retry_executor.execute(|| async {
-            let timer = RequestTimer::new();
-            let timestamp = RequestSigner::current_timestamp();
-            let signature = self.signer.sign_request("GET", "/health", b"", timestamp);
-            
-            // Log request
-            let timestamp_str = timestamp.to_string();
-            let headers = vec![
-                ("X-Timestamp", timestamp_str.as_str()),
-                ("X-Signature", signature.as_str()),
-            ];
-            self.logger.log_request("GET", &url, &headers, b"");
-            
-            match self.http_client
-                .get(&url)
-                .header("X-Timestamp", timestamp.to_string())
-                .header("X-Signature", signature)
-                .send()
-                .await
-            {
-                Ok(response) => {
-                    let status = response.status().as_u16();
-                    let response_headers = vec![]; // Would extract from response
-                    
-                    match response.status() {
-                        StatusCode::OK => {
-                            let body = response.bytes().await?;
-                            self.logger.log_response(status, &response_headers, &body, timer.elapsed());
-                            
-                            let health: HealthResponse = serde_json::from_slice(&body)?;
-                            Ok(health)
-                        }
-                        _ => {
-                            let body = response.bytes().await?;
-                            self.logger.log_response(status, &response_headers, &body, timer.elapsed());
-                            self.handle_error_status(StatusCode::from_u16(status).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR))
-                        }
-                    }
-                }
-                Err(e) => {
-                    self.logger.log_error("GET", &url, &e.to_string(), timer.elapsed());
-                    Err(e.into())
-                }
-            }
-        }).await
+        let body = self.send_raw(ApiRequest::get("/health").unauthenticated()).await?;
+        // Unlike the API endpoints, an undecodable health body is a serialization error
+        Ok(serde_json::from_slice(&body)?)
     }
     
     /// Exchanges an SSO token for an Authority Fabric token
@@ -232,16 +283,17 @@ impl Client {
     /// # #[tokio::main]
     /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
     /// # let client = Client::new("https://api.truthlinked.org", "tl_pro_...")?;
-    /// use rand::Rng;
+    /// use truthlinked_sdk::{ChannelBinding, NonceGenerator};
     /// 
-    /// let nonce: [u8; 32] = rand::thread_rng().gen();
-    /// let channel_binding: [u8; 32] = rand::thread_rng().gen();
+    /// let nonces = NonceGenerator::new();
+    /// # let server_cert_der: Vec<u8> = vec![];
+    /// let channel_binding = ChannelBinding::from_certificate_der(&server_cert_der);
     /// 
     /// let response = client.exchange_token(
     ///     "eyJ0eXAiOiJKV1QiLCJhbGc...",
     ///     vec!["read:users".to_string()],
-    ///     nonce,
-    ///     channel_binding,
+    ///     nonces.generate(),
+    ///     channel_binding.into(),
     /// ).await?;
     /// 
     /// println!("AF Token: {}", response.af_token);
@@ -255,8 +307,21 @@ impl Client {
         nonce: [u8; 32],
         channel_binding: [u8; 32],
     ) -> Result<TokenResponse> {
-        let url = format!("{}/v1/tokens", self.base_url);
-        
+        self.exchange_token_with_key(sso_token, requested_scope, nonce, channel_binding, IdempotencyKey::generate())
+            .await
+    }
+    
+    /// Exchange SSO token for AF token, deduplicated by `key`
+    /// 
+    /// [`exchange_token`](Self::exchange_token) generates a fresh key per call.
+    pub async fn exchange_token_with_key(
+        &self,
+        sso_token: impl Into<String>,
+        requested_scope: Vec<String>,
+        nonce: [u8; 32],
+        channel_binding: [u8; 32],
+        key: IdempotencyKey,
+    ) -> Result<TokenResponse> {
         let request = TokenRequest {
             sso_token: sso_token.into(),
             requested_scope,
@@ -264,27 +329,111 @@ impl Client {
             channel_binding: hex::encode(channel_binding),
         };
         
-        let response = self.http_client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .json(&request)
-            .send()
-            .await?;
-        
-        self.handle_response(response).await
+        self.send(ApiRequest::post("/v1/tokens", &request)?.idempotency_key(key)).await
     }
     
     /// Validate AF token
     pub async fn validate_token(&self, token_id: impl Into<String>) -> Result<ValidateResponse> {
-        let url = format!("{}/v1/tokens/{}/validate", self.base_url, token_id.into());
+        let path = format!("/v1/tokens/{}/validate", token_id.into());
+        
+        self.send(ApiRequest::get(path)).await
+    }
+    
+    /// Validate a complete AF token, as presented by a caller
+    /// 
+    /// Uses `POST /v1/validate`, the endpoint the TypeScript SDK's
+    /// `validateToken` calls. The token travels in the request body, which is
+    /// redacted in logs, rather than in the URL as with
+    /// [`validate_token`](Self::validate_token).
+    pub async fn validate_bearer_token(&self, token: &str) -> Result<ValidateResponse> {
+        let request = ValidateTokenRequest {
+            token: token.to_string(),
+        };
         
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
+        self.send(ApiRequest::post("/v1/validate", &request)?).await
+    }
+    
+    /// Check whether an AF token grants a permission
+    /// 
+    /// A 403 from the server is a denial, not an error, and is returned as
+    /// `authorized: false` without a reason or decision id.
+    /// 
+    /// # Example
+    /// ```rust,no_run
+    /// # use truthlinked_sdk::Client;
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("https://api.truthlinked.org", "key")?;
+    /// # let af_token = "";
+    /// let decision = client.authorize(af_token, "read:users").await?;
+    /// if !decision.authorized {
+    ///     println!("Denied: {:?} ({:?})", decision.reason, decision.decision_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn authorize(
+        &self,
+        token: impl Into<String>,
+        permission: impl Into<String>,
+    ) -> Result<AuthorizationDecision> {
+        let request = AuthorizationRequest {
+            token: token.into(),
+            permission: permission.into(),
+        };
+        
+        match self.send(ApiRequest::post("/v1/authorize", &request)?).await {
+            Err(TruthlinkedError::Forbidden) => Ok(AuthorizationDecision {
+                authorized: false,
+                reason: None,
+                decision_id: None,
+            }),
+            result => result,
+        }
+    }
+    
+    /// Evaluate several (token, permission) pairs in one call
+    /// 
+    /// Decisions are returned in the order of `requests`. Unlike
+    /// [`authorize`](Self::authorize), a 403 for the whole batch is returned
+    /// as `Forbidden`, since it cannot be attributed to a single pair.
+    pub async fn authorize_many<T, P>(
+        &self,
+        requests: impl IntoIterator<Item = (T, P)>,
+    ) -> Result<Vec<AuthorizationDecision>>
+    where
+        T: Into<String>,
+        P: Into<String>,
+    {
+        let request = BatchAuthorizationRequest {
+            requests: requests
+                .into_iter()
+                .map(|(token, permission)| AuthorizationRequest {
+                    token: token.into(),
+                    permission: permission.into(),
+                })
+                .collect(),
+        };
+        if request.requests.is_empty() {
+            return Ok(Vec::new());
+        }
+        
+        let response: BatchAuthorizationResponse = self
+            .send(ApiRequest::post("/v1/authorize/batch", &request)?)
             .await?;
+        if response.decisions.len() != request.requests.len() {
+            return Err(TruthlinkedError::InvalidResponse);
+        }
         
-        self.handle_response(response).await
+        Ok(response.decisions)
+    }
+    
+    /// Get the public keys the token issuer currently signs AF tokens with
+    /// 
+    /// Used by [`TokenVerifier`](crate::TokenVerifier) to validate tokens
+    /// locally.
+    pub async fn get_token_keys(&self) -> Result<TokenKeySet> {
+        self.send(ApiRequest::get("/v1/tokens/keys")).await
     }
     
     /// Retrieves shadow decisions showing breach prevention activity
@@ -317,224 +466,612 @@ impl Client {
     /// # }
     /// ```
     pub async fn get_shadow_decisions(&self) -> Result<Vec<ShadowDecision>> {
-        let url = format!("{}/v1/shadow/decisions", self.base_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
+        self.send(ApiRequest::get("/v1/shadow/decisions")).await
+    }
+    
+    /// Get one page of shadow decisions matching `query`
+    ///
+    /// Pass the previous page's `next_cursor` to continue. With `wait`, the
+    /// server may hold the request open until new decisions arrive.
+    pub async fn get_shadow_decision_page(
+        &self,
+        query: &ShadowDecisionQuery,
+        cursor: Option<&str>,
+        wait: Option<Duration>,
+    ) -> Result<Page<ShadowDecision>> {
+        self.shadow_decision_page(query, cursor, 0, wait).await
+    }
+    
+    /// Fetch a page, long-polling until it holds more than `seen` decisions
+    async fn shadow_decision_page(
+        &self,
+        query: &ShadowDecisionQuery,
+        cursor: Option<&str>,
+        seen: usize,
+        wait: Option<Duration>,
+    ) -> Result<Page<ShadowDecision>> {
+        let mut request = ApiRequest::get("/v1/shadow/decisions");
+        for (name, value) in query.params() {
+            request = request.query(name, value);
+        }
+        if let Some(cursor) = cursor {
+            request = request.query("cursor", cursor);
+        }
+        if seen > 0 {
+            request = request.query("offset", seen);
+        }
+        if let Some(wait) = wait {
+            request = request.query("wait", wait.as_secs());
+        }
+        self.send(request).await
+    }
+    
+    /// Stream every shadow decision matching `query`, fetching pages lazily
+    ///
+    /// The stream ends after the newest decision; use
+    /// [`follow_shadow_decisions`](Self::follow_shadow_decisions) to keep
+    /// receiving new ones.
+    pub fn shadow_decision_stream(&self, query: ShadowDecisionQuery) -> PageStream<'_, ShadowDecision> {
+        PageStream::new(Box::new(move |cursor: Option<String>, _seen| -> PageFuture<'_, ShadowDecision> {
+            let query = query.clone();
+            Box::pin(async move { self.get_shadow_decision_page(&query, cursor.as_deref(), None).await })
+        }))
+        .client_retries_rate_limits(self.retries_rate_limits("/v1/shadow/decisions"))
+    }
+    
+    /// Stream shadow decisions matching `query`, then wait for new ones
+    ///
+    /// Once caught up, the stream long-polls for new divergences and never
+    /// ends on its own. Polls of the newest page pass how many of its
+    /// decisions were already received as `offset`, so the server holds the
+    /// request until there is something new. Delivery is at-least-once: persist
+    /// [`PageStream::checkpoint`] after handling each decision and pass it to
+    /// [`PageStream::resume_from`] on restart.
+    ///
+    /// # Example
+    /// ```rust,no_run
+    /// use futures_util::StreamExt;
+    /// use truthlinked_sdk::{Client, ShadowDecisionQuery};
+    ///
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("https://api.truthlinked.org", "key")?;
+    /// let mut feed = client.follow_shadow_decisions(ShadowDecisionQuery::new().breach_prevented(true));
+    /// while let Some(decision) = feed.next().await {
+    ///     println!("Breach prevented: {}", decision?.divergence_id);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn follow_shadow_decisions(&self, query: ShadowDecisionQuery) -> PageStream<'_, ShadowDecision> {
+        let wait = query.wait_or_default();
+        PageStream::new(Box::new(move |cursor: Option<String>, seen| -> PageFuture<'_, ShadowDecision> {
+            let query = query.clone();
+            Box::pin(async move { self.shadow_decision_page(&query, cursor.as_deref(), seen, Some(wait)).await })
+        }))
+        .client_retries_rate_limits(self.retries_rate_limits("/v1/shadow/decisions"))
+        .follow()
+    }
+    
+    /// Get the AF policy for local shadow evaluation
+    /// 
+    /// See [`ShadowEvaluator`](crate::ShadowEvaluator).
+    pub async fn get_shadow_policy(&self) -> Result<ShadowPolicy> {
+        self.send(ApiRequest::get("/v1/shadow/policy")).await
+    }
+    
+    /// Upload shadow decisions evaluated locally
+    /// 
+    /// Usually called through [`ShadowEvaluator::flush`](crate::ShadowEvaluator::flush).
+    pub async fn submit_shadow_decisions(&self, decisions: &[ShadowDecision]) -> Result<()> {
+        self.submit_shadow_decisions_with_key(decisions, IdempotencyKey::generate()).await
+    }
+    
+    /// Upload shadow decisions evaluated locally, deduplicated by `key`
+    /// 
+    /// [`submit_shadow_decisions`](Self::submit_shadow_decisions) generates a
+    /// fresh key per call; reuse a key to resend the same batch without
+    /// storing it twice.
+    pub async fn submit_shadow_decisions_with_key(&self, decisions: &[ShadowDecision], key: IdempotencyKey) -> Result<()> {
+        let batch = ShadowDecisionBatch {
+            decisions: decisions.to_vec(),
+        };
         
-        self.handle_response(response).await
+        self.send_raw(ApiRequest::post("/v1/shadow/decisions", &batch)?.idempotency_key(key)).await?;
+        Ok(())
     }
     
     /// Replay IAM logs through AF policy engine
+    /// 
+    /// `adapter` is an [`IamAdapter`](crate::IamAdapter) or its name. Logs are
+    /// sent as given; use [`IamAdapter::parse_lines`](crate::IamAdapter::parse_lines)
+    /// to catch malformed ones first.
     pub async fn replay_iam_logs(
         &self,
         logs: Vec<String>,
         adapter: impl Into<String>,
     ) -> Result<ReplayResponse> {
-        let url = format!("{}/v1/shadow/replay", self.base_url);
-        
+        self.replay_iam_logs_with_key(logs, adapter, IdempotencyKey::generate()).await
+    }
+    
+    /// Replay IAM logs through AF policy engine, deduplicated by `key`
+    /// 
+    /// [`replay_iam_logs`](Self::replay_iam_logs) generates a fresh key per
+    /// call; reuse a key to resend the same logs without counting them twice.
+    pub async fn replay_iam_logs_with_key(
+        &self,
+        logs: Vec<String>,
+        adapter: impl Into<String>,
+        key: IdempotencyKey,
+    ) -> Result<ReplayResponse> {
         let request = ReplayRequest {
             logs,
             adapter: adapter.into(),
         };
         
-        let response = self.http_client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .json(&request)
-            .send()
-            .await?;
+        self.send(ApiRequest::post("/v1/shadow/replay", &request)?.idempotency_key(key)).await
+    }
+    
+    /// Replay IAM logs from an iterator of lines, uploading in chunks
+    /// 
+    /// Unlike [`replay_iam_logs`](Self::replay_iam_logs), only one chunk is
+    /// held in memory at a time. Returns the counters summed over all chunks.
+    /// On error, the last progress reported to
+    /// [`ReplayUpload::on_progress`] can be passed to
+    /// [`ReplayUpload::resume_from`] to continue after the last acknowledged
+    /// chunk.
+    pub async fn replay_iam_log_lines<I>(&self, lines: I, mut upload: ReplayUpload) -> Result<ReplayProgress>
+    where
+        I: IntoIterator,
+        I::Item: Into<String>,
+    {
+        let mut chunker = Chunker::new(&upload);
+        for line in lines {
+            if let Some(chunk) = chunker.push(line.into()) {
+                self.replay_chunk(chunk, &mut upload).await?;
+            }
+        }
+        if let Some(chunk) = chunker.finish() {
+            self.replay_chunk(chunk, &mut upload).await?;
+        }
         
-        self.handle_response(response).await
+        Ok(upload.progress)
     }
     
-    /// Get SOX compliance report
-    pub async fn get_sox_report(&self) -> Result<SoxReport> {
-        let url = format!("{}/v1/compliance/sox", self.base_url);
+    /// Replay newline-delimited IAM logs from a reader, uploading in chunks
+    /// 
+    /// Suited to multi-gigabyte exports: the input is read incrementally and
+    /// only one chunk is held in memory. See
+    /// [`replay_iam_log_lines`](Self::replay_iam_log_lines) for progress and
+    /// resumption.
+    /// 
+    /// # Example
+    /// ```rust,no_run
+    /// # use truthlinked_sdk::Client;
+    /// use truthlinked_sdk::ReplayUpload;
+    /// 
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("https://api.truthlinked.org", "key")?;
+    /// let file = tokio::fs::File::open("cloudtrail.jsonl").await?;
+    /// let upload = ReplayUpload::new("aws-cloudtrail")
+    ///     .on_progress(|progress| println!("{} chunks done", progress.chunks_acknowledged));
+    /// 
+    /// let result = client.replay_iam_log_reader(file, upload).await?;
+    /// println!("Breaches prevented: {}", result.totals.breaches_prevented);
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn replay_iam_log_reader<R>(&self, reader: R, mut upload: ReplayUpload) -> Result<ReplayProgress>
+    where
+        R: AsyncRead + Unpin,
+    {
+        let mut chunker = Chunker::new(&upload);
+        let mut lines = BufReader::new(reader).lines();
+        loop {
+            let line = lines
+                .next_line()
+                .await
+                .map_err(|e| TruthlinkedError::Storage(format!("Failed to read logs: {}", e)))?;
+            let Some(line) = line else { break };
+            
+            if let Some(chunk) = chunker.push(line) {
+                self.replay_chunk(chunk, &mut upload).await?;
+            }
+        }
+        if let Some(chunk) = chunker.finish() {
+            self.replay_chunk(chunk, &mut upload).await?;
+        }
         
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
+        Ok(upload.progress)
+    }
+    
+    /// Upload one replay chunk and record it as acknowledged
+    async fn replay_chunk(&self, chunk: Chunk, upload: &mut ReplayUpload) -> Result<()> {
+        for malformed in &chunk.rejected {
+            tracing::warn!(line = malformed.line, reason = %malformed.reason, "Skipping malformed log line");
+            if let Some(callback) = upload.on_malformed.as_mut() {
+                callback(malformed);
+            }
+        }
+        upload.progress.lines_rejected += chunk.rejected.len() as u64;
         
-        self.handle_response(response).await
+        if chunk.logs.is_empty() {
+            upload.progress.skip(chunk.input_lines);
+            if let Some(callback) = upload.on_progress.as_mut() {
+                callback(&upload.progress);
+            }
+            return Ok(());
+        }
+        
+        let request = ReplayRequest {
+            logs: chunk.logs,
+            adapter: upload.adapter.clone(),
+        };
+        let api_request = ApiRequest::post("/v1/shadow/replay", &request)?.idempotency_key(upload.chunk_key());
+        #[cfg(feature = "gzip")]
+        let api_request = if upload.gzip { api_request.gzip()? } else { api_request };
+        
+        let response: ReplayResponse = self.send(api_request).await?;
+        upload.progress.acknowledge(chunk.input_lines, &response);
+        if let Some(callback) = upload.on_progress.as_mut() {
+            callback(&upload.progress);
+        }
+        Ok(())
+    }
+    
+    /// Get SOX compliance report
+    pub async fn get_sox_report(&self) -> Result<SoxReport> {
+        self.get_sox_report_for(&ComplianceQuery::new()).await
+    }
+    
+    /// Get SOX compliance report for a time range and scope
+    /// 
+    /// # Example
+    /// ```rust,no_run
+    /// # use truthlinked_sdk::Client;
+    /// use truthlinked_sdk::ComplianceQuery;
+    /// 
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("https://api.truthlinked.org", "key")?;
+    /// let query = ComplianceQuery::new().period("2024-Q1".parse()?).account("finance");
+    /// let sox = client.get_sox_report_for(&query).await?;
+    /// 
+    /// for gap in &sox.gaps {
+    ///     println!("Audit trail gap: {}", gap);
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_sox_report_for(&self, query: &ComplianceQuery) -> Result<SoxReport> {
+        self.get_compliance_report(ComplianceFramework::Sox, query).await?.try_into()
     }
     
     /// Get PCI-DSS compliance report
     pub async fn get_pci_report(&self) -> Result<PciReport> {
-        let url = format!("{}/v1/compliance/pci", self.base_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
+        self.get_pci_report_for(&ComplianceQuery::new()).await
+    }
+    
+    /// Get PCI-DSS compliance report for a time range and scope
+    pub async fn get_pci_report_for(&self, query: &ComplianceQuery) -> Result<PciReport> {
+        self.get_compliance_report(ComplianceFramework::PciDss, query).await?.try_into()
+    }
+    
+    /// Get a compliance report for any supported framework
+    /// 
+    /// # Example
+    /// ```rust,no_run
+    /// # use truthlinked_sdk::Client;
+    /// use truthlinked_sdk::{ComplianceFramework, ComplianceQuery};
+    /// 
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+    /// # let client = Client::new("https://api.truthlinked.org", "key")?;
+    /// let query = ComplianceQuery::new().period("2024".parse()?);
+    /// let hipaa = client.get_compliance_report(ComplianceFramework::Hipaa, &query).await?;
+    /// 
+    /// for section in &hipaa.sections {
+    ///     println!("{}: {} findings", section.id, section.findings.len());
+    /// }
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn get_compliance_report(
+        &self,
+        framework: ComplianceFramework,
+        query: &ComplianceQuery,
+    ) -> Result<ComplianceReport> {
+        let request = query
+            .params()
+            .into_iter()
+            .fold(ApiRequest::get(format!("/v1/compliance/{}", framework)), |request, (name, value)| {
+                request.query(name, value)
+            });
         
-        self.handle_response(response).await
+        self.send(request).await
     }
     
     /// Get audit logs
     pub async fn get_audit_logs(&self) -> Result<Vec<AuditLog>> {
-        let url = format!("{}/v1/audit/logs", self.base_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
-        
-        self.handle_response(response).await
+        self.send(ApiRequest::get("/v1/audit/logs")).await
+    }
+    
+    /// Get one page of audit logs matching `query`
+    ///
+    /// Pass the previous page's `next_cursor` to continue. Most callers want
+    /// [`audit_log_stream`](Self::audit_log_stream) instead.
+    pub async fn get_audit_log_page(&self, query: &AuditLogQuery, cursor: Option<&str>) -> Result<Page<AuditLog>> {
+        let mut request = ApiRequest::get("/v1/audit/logs");
+        for (name, value) in query.params() {
+            request = request.query(name, value);
+        }
+        if let Some(cursor) = cursor {
+            request = request.query("cursor", cursor);
+        }
+        self.send(request).await
+    }
+    
+    /// Stream every audit log matching `query`, fetching pages lazily
+    ///
+    /// Rate-limited page fetches are retried with backoff (see
+    /// [`PageStream`] for how this combines with client retries). Save
+    /// [`PageStream::checkpoint`] to resume with [`PageStream::resume_from`]
+    /// after a restart.
+    pub fn audit_log_stream(&self, query: AuditLogQuery) -> PageStream<'_, AuditLog> {
+        PageStream::new(Box::new(move |cursor: Option<String>, _seen| -> PageFuture<'_, AuditLog> {
+            let query = query.clone();
+            Box::pin(async move { self.get_audit_log_page(&query, cursor.as_deref()).await })
+        }))
+        .client_retries_rate_limits(self.retries_rate_limits("/v1/audit/logs"))
     }
     
     /// Get usage statistics
     pub async fn get_usage(&self) -> Result<UsageResponse> {
-        let url = format!("{}/v1/usage", self.base_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
-        
-        self.handle_response(response).await
+        self.send(ApiRequest::get("/v1/usage")).await
     }
     
-    /// Handle HTTP response with proper error mapping
-    async fn handle_response<T: serde::de::DeserializeOwned>(
-        &self,
-        response: reqwest::Response,
-    ) -> Result<T> {
-        let status = response.status();
-        
-        match status {
-            StatusCode::OK => {
-                response.json::<T>().await.map_err(|_| TruthlinkedError::InvalidResponse)
-            }
-            StatusCode::UNAUTHORIZED => Err(TruthlinkedError::Unauthorized),
-            StatusCode::FORBIDDEN => Err(TruthlinkedError::Forbidden),
-            StatusCode::TOO_MANY_REQUESTS => {
-                let body = response.text().await.unwrap_or_default();
-                Err(TruthlinkedError::RateLimitExceeded(body))
-            }
-            StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
-                let body = response.text().await.unwrap_or_default();
-                Err(TruthlinkedError::InvalidRequest(body))
-            }
-            _ if status.is_server_error() => Err(TruthlinkedError::ServerError),
-            _ => Err(TruthlinkedError::InvalidResponse),
-        }
-    }
-
     // ========== Witness Chain Methods ==========
-
+    
     /// Submit event to witness chain
+    /// 
+    /// Use [`witness::SubmissionBuilder`](crate::witness::SubmissionBuilder)
+    /// to construct correctly hashed and signed submissions.
     pub async fn submit_witness(&self, submission: WitnessSubmission) -> Result<WitnessEvent> {
-        let url = format!("{}/witness/submit", self.base_url);
-        
-        let response = self.http_client
-            .post(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .json(&serde_json::json!({ "submission": submission }))
-            .send()
-            .await?;
+        self.submit_witness_with_key(submission, IdempotencyKey::generate()).await
+    }
+    
+    /// Submit event to witness chain, deduplicated by `key`
+    /// 
+    /// Pass [`IdempotencyKey::for_witness`] so that resubmitting the same AF
+    /// event, even from another process, cannot record it twice.
+    pub async fn submit_witness_with_key(
+        &self,
+        submission: WitnessSubmission,
+        key: IdempotencyKey,
+    ) -> Result<WitnessEvent> {
+        let body = serde_json::json!({ "submission": submission });
         
-        self.handle_response(response).await
+        self.send(ApiRequest::post("/witness/submit", &body)?.idempotency_key(key)).await
     }
-
+    
     /// Get witness event by sequence number
+    /// 
+    /// With `include_proof` set, the event carries a Merkle inclusion proof
+    /// that can be checked locally with [`witness::verify_inclusion`](crate::witness::verify_inclusion).
     pub async fn get_witness_event(&self, sequence: u64, include_proof: bool) -> Result<WitnessEvent> {
-        let url = format!("{}/witness/event/{}", self.base_url, sequence);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .query(&[("include_proof", include_proof.to_string())])
-            .send()
-            .await?;
+        let request = ApiRequest::get(format!("/witness/event/{}", sequence))
+            .query("include_proof", include_proof);
         
-        self.handle_response(response).await
+        self.send(request).await
     }
-
+    
     /// Get latest signed tree head
+    /// 
+    /// If the client was built with a witness keyring, the head's signature
+    /// is verified and `TruthlinkedError::Verification` is returned when it
+    /// does not check out.
     pub async fn get_latest_sth(&self) -> Result<SignedTreeHead> {
-        let url = format!("{}/witness/sth/latest", self.base_url);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
-        
-        self.handle_response(response).await
+        let sth = self.send(ApiRequest::get("/witness/sth/latest")).await?;
+        self.check_sth(sth)
     }
-
+    
     /// Get signed tree head at specific tree size
+    /// 
+    /// Verified against the witness keyring when one is configured, as for
+    /// [`get_latest_sth`](Self::get_latest_sth).
     pub async fn get_sth(&self, tree_size: u64) -> Result<SignedTreeHead> {
-        let url = format!("{}/witness/sth/{}", self.base_url, tree_size);
-        
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
+        let sth = self.send(ApiRequest::get(format!("/witness/sth/{}", tree_size))).await?;
+        self.check_sth(sth)
+    }
+    
+    /// Verify a tree head against the configured keyring, if any
+    fn check_sth(&self, sth: SignedTreeHead) -> Result<SignedTreeHead> {
+        if let Some(keyring) = &self.witness_keyring {
+            keyring.verify_sth(&sth)?;
+        }
+        Ok(sth)
+    }
+    
+    /// Get a consistency proof between two tree sizes
+    /// 
+    /// The proof shows that the tree of size `first_size` is a prefix of the
+    /// tree of size `second_size`. Check it locally with
+    /// [`witness::verify_consistency`](crate::witness::verify_consistency).
+    pub async fn get_consistency_proof(&self, first_size: u64, second_size: u64) -> Result<ConsistencyProof> {
+        let request = ApiRequest::get("/witness/consistency")
+            .query("first", first_size)
+            .query("second", second_size);
         
-        self.handle_response(response).await
+        self.send(request).await
     }
-
+    
     /// Export witness chain segment
+    /// 
+    /// Returns the raw export bundle (JSON Lines, one event per line). Parse
+    /// it with [`witness::parse_export`](crate::witness::parse_export) or
+    /// verify it offline with [`witness::verify_export`](crate::witness::verify_export).
     pub async fn export_witness_chain(&self, start_seq: Option<u64>, end_seq: Option<u64>) -> Result<Vec<u8>> {
-        let url = format!("{}/witness/export", self.base_url);
+        let mut request = ApiRequest::get("/witness/export");
         
-        let mut request = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()));
-
         if let Some(start) = start_seq {
-            request = request.query(&[("start_seq", start.to_string())]);
+            request = request.query("start_seq", start);
         }
         if let Some(end) = end_seq {
-            request = request.query(&[("end_seq", end.to_string())]);
+            request = request.query("end_seq", end);
         }
-
-        let response = request.send().await?;
         
-        if !response.status().is_success() {
-            return self.handle_error_status(response.status());
-        }
-
-        Ok(response.bytes().await?.to_vec())
+        self.send_raw(request).await
     }
-
+    
     /// Check witness chain health
     pub async fn witness_health(&self) -> Result<WitnessHealthResponse> {
-        let url = format!("{}/witness/health", self.base_url);
+        self.send(ApiRequest::get("/witness/health")).await
+    }
+    
+    // ========== Request Pipeline ==========
+    
+    /// Send a request through the pipeline and decode the JSON response
+    async fn send<T: DeserializeOwned>(&self, request: ApiRequest) -> Result<T> {
+        let body = self.send_raw(request).await?;
+        serde_json::from_slice(&body).map_err(|_| TruthlinkedError::InvalidResponse)
+    }
+    
+    /// Send a request through the pipeline and return the raw response body
+    ///
+    /// Every public endpoint goes through here so that signing, logging and
+    /// the configured retry policy apply uniformly.
+    async fn send_raw(&self, request: ApiRequest) -> Result<Vec<u8>> {
+        let url = format!("{}{}", self.base_url, request.path);
         
-        let response = self.http_client
-            .get(&url)
-            .header("Authorization", format!("Bearer {}", self.license_key.as_str()))
-            .send()
-            .await?;
+        self.retry_executor_for(&request.path)
+            .execute(|| self.attempt(&request, &url))
+            .await
+    }
+    
+    /// The executor of the most specific endpoint policy matching `path`
+    /// Whether requests to `path` already retry rate limits
+    fn retries_rate_limits(&self, path: &str) -> bool {
+        self.retry_executor_for(path).retries_rate_limits()
+    }
+    
+    fn retry_executor_for(&self, path: &str) -> &RetryExecutor {
+        self.endpoint_retry_executors
+            .iter()
+            .filter(|(prefix, _)| path.starts_with(prefix.as_str()))
+            .max_by_key(|(prefix, _)| prefix.len())
+            .map_or(&self.retry_executor, |(_, executor)| executor)
+    }
+    
+    /// Perform a single attempt: sign, log, send and classify
+    async fn attempt(&self, request: &ApiRequest, url: &str) -> Result<Vec<u8>> {
+        let timer = RequestTimer::new();
+        let method = request.method.as_str();
+        
+        // Sign (a fresh timestamp per attempt keeps retries within the replay window)
+        let timestamp = RequestSigner::current_timestamp();
+        let signature = self.signer.sign_request(method, &request.path, &request.body, timestamp);
+        let timestamp_str = timestamp.to_string();
+        let authorization = format!("Bearer {}", self.license_key.as_str());
+        
+        let mut headers = vec![
+            ("X-Timestamp", timestamp_str.as_str()),
+            ("X-Signature", signature.as_str()),
+        ];
+        if request.authenticated {
+            headers.push(("Authorization", authorization.as_str()));
+        }
+        if !request.body.is_empty() {
+            headers.push(("Content-Type", "application/json"));
+        }
+        if let Some(encoding) = request.content_encoding {
+            headers.push(("Content-Encoding", encoding));
+        }
+        if let Some(key) = &request.idempotency_key {
+            headers.push(("Idempotency-Key", key.as_str()));
+        }
+        
+        // Log
+        self.logger.log_request(method, url, &headers, &request.body);
+        
+        // Send
+        let mut builder = self.http_client.request(request.method.clone(), url);
+        if !request.query.is_empty() {
+            builder = builder.query(&request.query);
+        }
+        for (name, value) in &headers {
+            builder = builder.header(*name, *value);
+        }
+        if !request.body.is_empty() {
+            builder = builder.body(request.body.clone());
+        }
+        
+        let response = match builder.send().await {
+            Ok(response) => response,
+            Err(e) => {
+                self.logger.log_error(method, url, &e.to_string(), timer.elapsed());
+                return Err(e.into());
+            }
+        };
+        
+        // Classify
+        let status = response.status();
+        let header_map = response.headers().clone();
+        let response_headers: Vec<(String, String)> = response
+            .headers()
+            .iter()
+            .map(|(name, value)| {
+                (name.to_string(), value.to_str().unwrap_or("<binary>").to_string())
+            })
+            .collect();
+        
+        let body = match response.bytes().await {
+            Ok(body) => body,
+            Err(e) => {
+                self.logger.log_error(method, url, &e.to_string(), timer.elapsed());
+                return Err(e.into());
+            }
+        };
+        
+        let header_refs: Vec<(&str, &str)> = response_headers
+            .iter()
+            .map(|(name, value)| (name.as_str(), value.as_str()))
+            .collect();
+        self.logger.log_response(status.as_u16(), &header_refs, &body, timer.elapsed());
         
-        self.handle_response(response).await
+        if status.is_success() {
+            Ok(body.to_vec())
+        } else {
+            Err(Self::handle_error_status(status, &header_map, &body))
+        }
     }
-
-    /// Handle HTTP error status codes
-    fn handle_error_status<T>(&self, status: StatusCode) -> Result<T> {
+    
+    /// Map an HTTP error status (with its headers and body) to a `TruthlinkedError`
+    fn handle_error_status(status: StatusCode, headers: &HeaderMap, body: &[u8]) -> TruthlinkedError {
+        let message = |default: &str| {
+            let text = String::from_utf8_lossy(body).trim().to_string();
+            if text.is_empty() { default.to_string() } else { text }
+        };
+        
         match status {
-            StatusCode::UNAUTHORIZED => Err(TruthlinkedError::Unauthorized),
-            StatusCode::FORBIDDEN => Err(TruthlinkedError::Forbidden),
+            StatusCode::UNAUTHORIZED => TruthlinkedError::Unauthorized,
+            StatusCode::FORBIDDEN => TruthlinkedError::Forbidden,
             StatusCode::TOO_MANY_REQUESTS => {
-                Err(TruthlinkedError::RateLimitExceeded("Rate limit exceeded".to_string()))
+                TruthlinkedError::RateLimitExceeded(RateLimitInfo::from_headers(message("Rate limit exceeded"), headers))
+            }
+            StatusCode::SERVICE_UNAVAILABLE => {
+                let info = RateLimitInfo::from_headers(message("Service unavailable"), headers);
+                if info.has_hints() {
+                    TruthlinkedError::ServiceUnavailable(info)
+                } else {
+                    TruthlinkedError::ServerError
+                }
             }
             StatusCode::BAD_REQUEST | StatusCode::UNPROCESSABLE_ENTITY => {
-                Err(TruthlinkedError::InvalidRequest("Invalid request".to_string()))
+                TruthlinkedError::InvalidRequest(message("Invalid request"))
             }
-            _ if status.is_server_error() => Err(TruthlinkedError::ServerError),
-            _ => Err(TruthlinkedError::InvalidResponse),
+            _ if status.is_server_error() => TruthlinkedError::ServerError,
+            _ => TruthlinkedError::InvalidResponse,
         }
     }
 }
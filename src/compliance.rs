@@ -0,0 +1,476 @@
+use crate::error::TruthlinkedError;
+use crate::types::{PciReport, SoxReport};
+use chrono::{DateTime, Datelike, NaiveDate, SecondsFormat, Utc};
+use serde::{Deserialize, Deserializer, Serialize};
+use serde_json::Value;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::str::FromStr;
+
+/// Compliance framework a report is generated for
+///
+/// FedRAMP reports require the Government tier.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub enum ComplianceFramework {
+    /// Sarbanes-Oxley IT general controls
+    #[serde(rename = "sox")]
+    Sox,
+    /// PCI DSS
+    #[serde(rename = "pci")]
+    PciDss,
+    /// HIPAA Security Rule
+    #[serde(rename = "hipaa")]
+    Hipaa,
+    /// SOC 2 Trust Services Criteria
+    #[serde(rename = "soc2")]
+    Soc2,
+    /// ISO/IEC 27001 Annex A
+    #[serde(rename = "iso27001")]
+    Iso27001,
+    /// FedRAMP (NIST SP 800-53)
+    #[serde(rename = "fedramp")]
+    FedRamp,
+}
+
+impl ComplianceFramework {
+    /// All frameworks
+    pub const ALL: [ComplianceFramework; 6] = [
+        ComplianceFramework::Sox,
+        ComplianceFramework::PciDss,
+        ComplianceFramework::Hipaa,
+        ComplianceFramework::Soc2,
+        ComplianceFramework::Iso27001,
+        ComplianceFramework::FedRamp,
+    ];
+    
+    /// Name used in the API path, e.g. `"hipaa"`
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::Sox => "sox",
+            Self::PciDss => "pci",
+            Self::Hipaa => "hipaa",
+            Self::Soc2 => "soc2",
+            Self::Iso27001 => "iso27001",
+            Self::FedRamp => "fedramp",
+        }
+    }
+}
+
+impl fmt::Display for ComplianceFramework {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ComplianceFramework {
+    type Err = TruthlinkedError;
+    
+    fn from_str(name: &str) -> Result<Self, Self::Err> {
+        Self::ALL
+            .into_iter()
+            .find(|framework| framework.as_str().eq_ignore_ascii_case(name))
+            .ok_or_else(|| TruthlinkedError::InvalidRequest(format!("Unknown compliance framework: {}", name)))
+    }
+}
+
+/// Time span covered by a compliance report, as a half-open interval
+///
+/// Servers may report it as `{"start": ..., "end": ...}` or as a string: an
+/// ISO 8601 interval (`"2024-01-01/2024-03-31"`, dates inclusive), a quarter
+/// (`"2024-Q1"`), a month (`"2024-01"`), a year (`"2024"`) or a single day.
+/// A string in any other form, such as `"last month"`, does not decode, so
+/// a report carrying it fails with [`TruthlinkedError::InvalidResponse`].
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::ReportPeriod;
+///
+/// let q1: ReportPeriod = "2024-Q1".parse().unwrap();
+/// assert_eq!(q1, "2024-01-01/2024-03-31".parse().unwrap());
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub struct ReportPeriod {
+    /// First instant covered
+    pub start: DateTime<Utc>,
+    /// First instant after the period
+    pub end: DateTime<Utc>,
+}
+
+impl ReportPeriod {
+    /// Period from `start` up to (not including) `end`
+    pub fn new(start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        Self { start, end }
+    }
+    
+    /// Whether `instant` falls within the period
+    pub fn contains(&self, instant: DateTime<Utc>) -> bool {
+        self.start <= instant && instant < self.end
+    }
+    
+    /// Length of the period
+    pub fn duration(&self) -> chrono::Duration {
+        self.end - self.start
+    }
+}
+
+/// Midnight UTC at the start of `date`
+fn day_start(date: NaiveDate) -> DateTime<Utc> {
+    date.and_hms_opt(0, 0, 0).expect("midnight is valid").and_utc()
+}
+
+/// First day of the month `months` after January of `year`
+fn month_start(year: i32, months: u32) -> Option<DateTime<Utc>> {
+    let year = year.checked_add((months / 12) as i32)?;
+    NaiveDate::from_ymd_opt(year, months % 12 + 1, 1).map(day_start)
+}
+
+/// One end of an interval; a bare date as the end includes that whole day
+fn parse_bound(text: &str, is_end: bool) -> Option<DateTime<Utc>> {
+    if let Ok(instant) = DateTime::parse_from_rfc3339(text) {
+        return Some(instant.with_timezone(&Utc));
+    }
+    let date = NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()?;
+    if is_end {
+        date.succ_opt().map(day_start)
+    } else {
+        Some(day_start(date))
+    }
+}
+
+fn parse_period(text: &str) -> Option<ReportPeriod> {
+    let text = text.trim();
+    
+    let (start, end) = if let Some((start, end)) = text.split_once('/') {
+        (parse_bound(start, false)?, parse_bound(end, true)?)
+    } else if let Some((year, quarter)) = text.split_once("-Q") {
+        let year: i32 = year.parse().ok()?;
+        let quarter: u32 = quarter.parse().ok().filter(|q| (1..=4).contains(q))?;
+        (month_start(year, (quarter - 1) * 3)?, month_start(year, quarter * 3)?)
+    } else if text.len() == 4 {
+        let year: i32 = text.parse().ok()?;
+        (month_start(year, 0)?, month_start(year, 12)?)
+    } else if text.len() == 7 {
+        let date = NaiveDate::parse_from_str(&format!("{}-01", text), "%Y-%m-%d").ok()?;
+        let month = date.month0();
+        (month_start(date.year(), month)?, month_start(date.year(), month + 1)?)
+    } else {
+        (parse_bound(text, false)?, parse_bound(text, true)?)
+    };
+    
+    (start < end).then_some(ReportPeriod { start, end })
+}
+
+impl FromStr for ReportPeriod {
+    type Err = TruthlinkedError;
+    
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        parse_period(text).ok_or_else(|| TruthlinkedError::InvalidRequest(format!("Invalid report period: {}", text)))
+    }
+}
+
+impl fmt::Display for ReportPeriod {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}/{}",
+            self.start.to_rfc3339_opts(SecondsFormat::AutoSi, true),
+            self.end.to_rfc3339_opts(SecondsFormat::AutoSi, true)
+        )
+    }
+}
+
+impl<'de> Deserialize<'de> for ReportPeriod {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        #[derive(Deserialize)]
+        #[serde(untagged)]
+        enum Wire {
+            Text(String),
+            Bounds { start: DateTime<Utc>, end: DateTime<Utc> },
+        }
+        
+        match Wire::deserialize(deserializer)? {
+            Wire::Text(text) => parse_period(&text)
+                .ok_or_else(|| serde::de::Error::custom(format!("invalid report period: {}", text))),
+            Wire::Bounds { start, end } => Ok(ReportPeriod { start, end }),
+        }
+    }
+}
+
+/// Which reports to generate: time range and scope
+///
+/// Without a range the server reports on its default period.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::ComplianceQuery;
+///
+/// let query = ComplianceQuery::new()
+///     .period("2024-Q1".parse().unwrap())
+///     .account("123456789012");
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ComplianceQuery {
+    period: Option<ReportPeriod>,
+    account: Option<String>,
+    instance: Option<String>,
+}
+
+impl ComplianceQuery {
+    /// Query for the default period and the whole tenant
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Report on `period`
+    pub fn period(mut self, period: ReportPeriod) -> Self {
+        self.period = Some(period);
+        self
+    }
+    
+    /// Report on `start` up to (not including) `end`
+    pub fn range(self, start: DateTime<Utc>, end: DateTime<Utc>) -> Self {
+        self.period(ReportPeriod::new(start, end))
+    }
+    
+    /// Only events of `account`
+    pub fn account(mut self, account: impl Into<String>) -> Self {
+        self.account = Some(account.into());
+        self
+    }
+    
+    /// Only events of AF `instance`
+    pub fn instance(mut self, instance: impl Into<String>) -> Self {
+        self.instance = Some(instance.into());
+        self
+    }
+    
+    /// Query string parameters, in a stable order
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(period) = &self.period {
+            params.push(("from", period.start.to_rfc3339_opts(SecondsFormat::AutoSi, true)));
+            params.push(("to", period.end.to_rfc3339_opts(SecondsFormat::AutoSi, true)));
+        }
+        if let Some(account) = &self.account {
+            params.push(("account", account.clone()));
+        }
+        if let Some(instance) = &self.instance {
+            params.push(("instance", instance.clone()));
+        }
+        params
+    }
+}
+
+/// Outcome of one control
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum ControlStatus {
+    Pass,
+    Fail,
+    Partial,
+    NotApplicable,
+    /// A status this SDK version does not know
+    #[serde(other)]
+    Unknown,
+}
+
+/// Pointer to evidence backing a finding, e.g. a witness event or audit log
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct EvidenceRef {
+    /// Kind of evidence, e.g. `"witness_event"` or `"audit_log"`
+    #[serde(rename = "type")]
+    pub kind: String,
+    pub id: String,
+    #[serde(default)]
+    pub url: Option<String>,
+}
+
+/// Result of evaluating one control over the report period
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ComplianceFinding {
+    /// Framework control identifier, e.g. `"ITGC-AC-01"` or `"PCI-10.2.1"`
+    pub control_id: String,
+    pub status: ControlStatus,
+    #[serde(default)]
+    pub description: Option<String>,
+    #[serde(default)]
+    pub evidence: Vec<EvidenceRef>,
+    /// Intervals in which the control could not be evidenced
+    #[serde(default)]
+    pub gaps: Vec<ReportPeriod>,
+}
+
+/// Findings for one group of framework controls, e.g. HIPAA 164.312
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ControlSection {
+    /// Section identifier, e.g. `"164.312"`, `"CC6"` or `"A.8"`
+    pub id: String,
+    #[serde(default)]
+    pub title: Option<String>,
+    #[serde(default)]
+    pub findings: Vec<ComplianceFinding>,
+}
+
+/// Compliance report in the structure shared by all frameworks
+///
+/// Framework-specific summary values (e.g. SOX `audit_trail_complete`) are
+/// kept in [`checks`](Self::checks).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ComplianceReport {
+    pub period: ReportPeriod,
+    #[serde(default)]
+    pub total_events: Option<u64>,
+    /// Intervals missing from the audit trail
+    #[serde(default)]
+    pub gaps: Vec<ReportPeriod>,
+    /// Findings not grouped into a section
+    #[serde(default)]
+    pub findings: Vec<ComplianceFinding>,
+    /// Framework-specific control sections
+    #[serde(default)]
+    pub sections: Vec<ControlSection>,
+    /// Remaining framework-specific fields
+    #[serde(flatten)]
+    pub checks: BTreeMap<String, Value>,
+}
+
+impl ComplianceReport {
+    /// Boolean summary value `name`, e.g. `"audit_complete"`
+    pub fn check(&self, name: &str) -> Option<bool> {
+        self.checks.get(name).and_then(Value::as_bool)
+    }
+    
+    /// Section `id`, if present
+    pub fn section(&self, id: &str) -> Option<&ControlSection> {
+        self.sections.iter().find(|section| section.id == id)
+    }
+    
+    /// Every finding, top-level and in sections
+    pub fn all_findings(&self) -> impl Iterator<Item = &ComplianceFinding> {
+        self.findings
+            .iter()
+            .chain(self.sections.iter().flat_map(|section| section.findings.iter()))
+    }
+    
+    fn require_check(&self, name: &str) -> Result<bool, TruthlinkedError> {
+        self.check(name).ok_or_else(|| {
+            tracing::warn!(check = name, "Compliance report is missing a summary check");
+            TruthlinkedError::InvalidResponse
+        })
+    }
+}
+
+impl TryFrom<ComplianceReport> for SoxReport {
+    type Error = TruthlinkedError;
+    
+    fn try_from(report: ComplianceReport) -> Result<Self, Self::Error> {
+        Ok(SoxReport {
+            total_events: report.total_events.ok_or(TruthlinkedError::InvalidResponse)?,
+            audit_trail_complete: report.require_check("audit_trail_complete")?,
+            no_gaps: report.require_check("no_gaps")?,
+            findings: report.all_findings().cloned().collect(),
+            period: report.period,
+            gaps: report.gaps,
+        })
+    }
+}
+
+impl TryFrom<ComplianceReport> for PciReport {
+    type Error = TruthlinkedError;
+    
+    fn try_from(report: ComplianceReport) -> Result<Self, Self::Error> {
+        Ok(PciReport {
+            access_controls_enforced: report.require_check("access_controls_enforced")?,
+            encryption_verified: report.require_check("encryption_verified")?,
+            audit_complete: report.require_check("audit_complete")?,
+            findings: report.all_findings().cloned().collect(),
+            period: report.period,
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use chrono::TimeZone;
+    
+    fn utc(y: i32, m: u32, d: u32) -> DateTime<Utc> {
+        Utc.with_ymd_and_hms(y, m, d, 0, 0, 0).unwrap()
+    }
+    
+    #[test]
+    fn test_parse_period_formats() {
+        let q4 = ReportPeriod::new(utc(2024, 10, 1), utc(2025, 1, 1));
+        assert_eq!("2024-Q4".parse::<ReportPeriod>().unwrap(), q4);
+        assert_eq!("2024-10-01/2024-12-31".parse::<ReportPeriod>().unwrap(), q4);
+        assert_eq!("2024-10-01T00:00:00Z/2025-01-01T00:00:00Z".parse::<ReportPeriod>().unwrap(), q4);
+        
+        assert_eq!("2024".parse::<ReportPeriod>().unwrap(), ReportPeriod::new(utc(2024, 1, 1), utc(2025, 1, 1)));
+        assert_eq!("2024-12".parse::<ReportPeriod>().unwrap(), ReportPeriod::new(utc(2024, 12, 1), utc(2025, 1, 1)));
+        assert_eq!("2024-02-29".parse::<ReportPeriod>().unwrap(), ReportPeriod::new(utc(2024, 2, 29), utc(2024, 3, 1)));
+    }
+    
+    #[test]
+    fn test_parse_period_rejects_garbage() {
+        for text in ["", "Q1", "2024-Q5", "2024-13", "2024-03-01/2024-01-01", "last month"] {
+            assert!(text.parse::<ReportPeriod>().is_err(), "{}", text);
+        }
+    }
+    
+    #[test]
+    fn test_period_serde() {
+        let from_object: ReportPeriod =
+            serde_json::from_str(r#"{"start":"2024-01-01T00:00:00Z","end":"2024-04-01T00:00:00Z"}"#).unwrap();
+        let from_string: ReportPeriod = serde_json::from_str(r#""2024-Q1""#).unwrap();
+        assert_eq!(from_object, from_string);
+        assert_eq!(from_string.to_string(), "2024-01-01T00:00:00Z/2024-04-01T00:00:00Z");
+        
+        let round_trip: ReportPeriod = serde_json::from_str(&serde_json::to_string(&from_string).unwrap()).unwrap();
+        assert_eq!(round_trip, from_string);
+    }
+    
+    #[test]
+    fn test_query_params() {
+        let query = ComplianceQuery::new()
+            .instance("af-1")
+            .range(utc(2024, 1, 1), utc(2024, 2, 1));
+        
+        assert_eq!(
+            query.params(),
+            vec![
+                ("from", "2024-01-01T00:00:00Z".to_string()),
+                ("to", "2024-02-01T00:00:00Z".to_string()),
+                ("instance", "af-1".to_string()),
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_framework_names() {
+        for framework in ComplianceFramework::ALL {
+            assert_eq!(framework.as_str().parse::<ComplianceFramework>().unwrap(), framework);
+            assert_eq!(serde_json::to_value(framework).unwrap(), framework.as_str());
+        }
+        assert_eq!("FedRAMP".parse::<ComplianceFramework>().unwrap(), ComplianceFramework::FedRamp);
+        assert!("gdpr".parse::<ComplianceFramework>().is_err());
+    }
+    
+    #[test]
+    fn test_sox_from_common_report() {
+        let report: ComplianceReport = serde_json::from_value(serde_json::json!({
+            "period": "2024",
+            "total_events": 9,
+            "audit_trail_complete": true,
+            "no_gaps": true,
+            "sections": [{ "id": "ITGC-LOG", "findings": [{ "control_id": "ITGC-LOG-01", "status": "pass" }] }]
+        }))
+        .unwrap();
+        
+        assert_eq!(report.check("no_gaps"), Some(true));
+        let sox = SoxReport::try_from(report.clone()).unwrap();
+        assert_eq!(sox.total_events, 9);
+        assert_eq!(sox.findings[0].control_id, "ITGC-LOG-01");
+        
+        assert!(matches!(PciReport::try_from(report), Err(TruthlinkedError::InvalidResponse)));
+    }
+}
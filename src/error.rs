@@ -1,3 +1,6 @@
+use reqwest::header::{HeaderMap, RETRY_AFTER};
+use std::fmt;
+use std::time::Duration;
 use thiserror::Error;
 
 /// Errors that can occur when using the Truthlinked SDK
@@ -10,7 +13,11 @@ use thiserror::Error;
 /// - Internal system details are not exposed
 /// - Error messages are safe to log and display to users
 /// - Stack traces do not contain sensitive information
+/// 
+/// The enum is `#[non_exhaustive]`: new variants may be added in minor
+/// releases, so `match` on it needs a wildcard arm.
 #[derive(Error, Debug)]
+#[non_exhaustive]
 pub enum TruthlinkedError {
     /// Network-related errors (connection failures, timeouts, DNS resolution)
     /// 
@@ -52,9 +59,13 @@ pub enum TruthlinkedError {
     /// - Professional: 500,000 requests/month
     /// - Enterprise: Unlimited
     /// 
+    /// The server's `Retry-After` and `X-RateLimit-*` headers, when sent,
+    /// are available on the [`RateLimitInfo`]. The payload used to be
+    /// the message `String`, now [`RateLimitInfo::message`].
+    /// 
     /// Resolution: Wait for the limit to reset or upgrade your tier
     #[error("Rate limit exceeded: {0}")]
-    RateLimitExceeded(String),
+    RateLimitExceeded(RateLimitInfo),
     
     /// Request validation failed due to invalid parameters
     /// 
@@ -74,6 +85,15 @@ pub enum TruthlinkedError {
     #[error("Server error")]
     ServerError,
     
+    /// The service is temporarily unavailable and advised when to retry
+    /// 
+    /// Returned for 503 responses carrying `Retry-After` or rate-limit
+    /// headers; other 503 responses are reported as `ServerError`.
+    /// 
+    /// Resolution: Retry after the advised interval
+    #[error("Service unavailable: {0}")]
+    ServiceUnavailable(RateLimitInfo),
+    
     /// Failed to serialize or deserialize request/response data
     /// 
     /// This error indicates a problem with JSON serialization/deserialization,
@@ -95,12 +115,223 @@ pub enum TruthlinkedError {
     /// Resolution: Contact support to renew your license
     #[error("License expired")]
     LicenseExpired,
+    
+    /// Reading or writing local SDK state failed
+    /// 
+    /// This error occurs when a persistent store used by the SDK (for
+    /// example the witness monitor's tree head file) cannot be accessed.
+    /// 
+    /// Resolution: Check the path and file permissions
+    #[error("Storage error: {0}")]
+    Storage(String),
+    
+    /// Local verification of server-provided data failed
+    /// 
+    /// This error indicates that a proof, signature or hash returned by the
+    /// server did not check out. Unlike the other variants it is never
+    /// transient: retrying will not help, and the data must not be trusted.
+    #[error("Verification failed: {0}")]
+    Verification(#[from] VerificationError),
+    
+    /// The server's certificate chain matched none of the configured pins
+    /// 
+    /// The chain itself was valid, so this points to a key rotation without
+    /// a matching backup pin, or to an intercepting proxy. It is never retried.
+    /// 
+    /// Resolution: Check the pins passed to `ClientBuilder::certificate_pin`
+    #[error("Certificate pin mismatch")]
+    CertificatePinMismatch,
+}
+
+impl TruthlinkedError {
+    /// Throttling details, for rate-limited and unavailable responses
+    pub fn rate_limit(&self) -> Option<&RateLimitInfo> {
+        match self {
+            TruthlinkedError::RateLimitExceeded(info) | TruthlinkedError::ServiceUnavailable(info) => Some(info),
+            _ => None,
+        }
+    }
+    
+    /// How long the server asked clients to wait before retrying
+    pub fn retry_after(&self) -> Option<Duration> {
+        self.rate_limit().and_then(|info| info.retry_after)
+    }
+}
+
+/// Throttling details from a 429 or 503 response
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RateLimitInfo {
+    /// Error message from the response body
+    pub message: String,
+    /// How long to wait before retrying (`Retry-After`)
+    pub retry_after: Option<Duration>,
+    /// Requests left in the current window (`X-RateLimit-Remaining`)
+    pub remaining: Option<u64>,
+    /// Time until the current window resets (`X-RateLimit-Reset`)
+    pub reset_after: Option<Duration>,
+}
+
+impl RateLimitInfo {
+    /// Parse the throttling headers of a response
+    /// 
+    /// `Retry-After` may be delay-seconds or an HTTP date. `X-RateLimit-Reset`
+    /// may be delta seconds or, for values past 1e9, a Unix timestamp.
+    pub(crate) fn from_headers(message: String, headers: &HeaderMap) -> Self {
+        let now = crate::signing::RequestSigner::current_timestamp();
+        let header = |name: &str| headers.get(name).and_then(|value| value.to_str().ok()).map(str::trim);
+        
+        let retry_after = header(RETRY_AFTER.as_str()).and_then(|value| match value.parse::<u64>() {
+            Ok(seconds) => Some(Duration::from_secs(seconds)),
+            Err(_) => chrono::DateTime::parse_from_rfc2822(value)
+                .ok()
+                .map(|date| Duration::from_secs((date.timestamp().max(0) as u64).saturating_sub(now))),
+        });
+        let reset_after = header("x-ratelimit-reset")
+            .and_then(|value| value.parse::<u64>().ok())
+            .map(|reset| {
+                if reset > 1_000_000_000 {
+                    Duration::from_secs(reset.saturating_sub(now))
+                } else {
+                    Duration::from_secs(reset)
+                }
+            });
+        
+        Self {
+            message,
+            retry_after,
+            remaining: header("x-ratelimit-remaining").and_then(|value| value.parse().ok()),
+            reset_after,
+        }
+    }
+    
+    /// Whether the server sent any throttling headers
+    pub(crate) fn has_hints(&self) -> bool {
+        self.retry_after.is_some() || self.remaining.is_some() || self.reset_after.is_some()
+    }
+    
+    /// Interval the server advised waiting for, if any
+    /// 
+    /// Prefers `Retry-After`, falling back to the reset time once the window
+    /// is exhausted.
+    pub fn advised_delay(&self) -> Option<Duration> {
+        self.retry_after.or(match self.remaining {
+            Some(0) => self.reset_after,
+            _ => None,
+        })
+    }
+}
+
+impl fmt::Display for RateLimitInfo {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl From<String> for RateLimitInfo {
+    fn from(message: String) -> Self {
+        Self { message, ..Self::default() }
+    }
+}
+
+impl From<&str> for RateLimitInfo {
+    fn from(message: &str) -> Self {
+        message.to_string().into()
+    }
+}
+
+/// Reasons a client-side verification can fail
+/// 
+/// Each variant names the step that failed so that auditors can tell a
+/// malformed response apart from evidence of tampering.
+#[derive(Error, Debug, Clone, PartialEq)]
+pub enum VerificationError {
+    /// The event was fetched without a proof (`include_proof: false`)
+    #[error("event does not carry an inclusion proof")]
+    MissingProof,
+    
+    /// The proof could not be decoded
+    #[error("proof is malformed")]
+    MalformedProof,
+    
+    /// A hash field was not a valid hex-encoded 32-byte SHA-256 value
+    #[error("invalid hash in field `{0}`")]
+    InvalidHash(&'static str),
+    
+    /// The proof was generated for a different tree size than the tree head
+    #[error("proof is for tree size {proof} but tree head has size {tree_head}")]
+    TreeSizeMismatch { proof: u64, tree_head: u64 },
+    
+    /// The leaf index does not exist in a tree of the given size
+    #[error("leaf index {leaf_index} is outside a tree of size {tree_size}")]
+    LeafIndexOutOfRange { leaf_index: u64, tree_size: u64 },
+    
+    /// The audit path has the wrong number of hashes for the leaf position
+    #[error("audit path of length {path_length} does not fit leaf {leaf_index} in a tree of size {tree_size}")]
+    InvalidPathLength { leaf_index: u64, tree_size: u64, path_length: usize },
+    
+    /// The recomputed root does not match the signed tree head
+    #[error("computed root {computed} does not match expected root {expected}")]
+    RootMismatch { expected: String, computed: String },
+    
+    /// The newer tree head is smaller than the older one (rollback)
+    #[error("tree shrank from size {old} to size {new}")]
+    TreeShrunk { old: u64, new: u64 },
+    
+    /// The consistency path has the wrong shape for the two tree sizes
+    #[error("consistency path does not fit the tree sizes")]
+    InvalidConsistencyPath,
+    
+    /// A line of an export bundle is not a valid witness event
+    #[error("export line {line} is not a valid witness event")]
+    MalformedExport { line: usize },
+    
+    /// Events are not consecutive
+    #[error("expected sequence {expected} but found {found}")]
+    SequenceGap { expected: u64, found: u64 },
+    
+    /// No event can follow the largest possible sequence number
+    #[error("no event can follow sequence {sequence}")]
+    SequenceOverflow { sequence: u64 },
+    
+    /// An event's `prev_hash` does not match the previous event's hash
+    #[error("hash chain broken at sequence {sequence}")]
+    ChainBroken { sequence: u64 },
+    
+    /// An event's `event_hash` does not match its recomputed hash
+    #[error("event hash mismatch at sequence {sequence}")]
+    EventHashMismatch { sequence: u64 },
+    
+    /// The export does not contain exactly the events covered by the tree head
+    #[error("export contains {events} events but tree head has size {tree_size}")]
+    EventCountMismatch { events: u64, tree_size: u64 },
+    
+    /// A chain segment not starting at genesis cannot be checked against a root
+    #[error("chain segment does not start at genesis")]
+    PartialChain,
+    
+    /// No trusted key is configured for the tree head's key version
+    #[error("no trusted key for key version {0}")]
+    UnknownKeyVersion(u32),
+    
+    /// The signature was not a valid base64-encoded Ed25519 signature
+    #[error("signature is malformed")]
+    MalformedSignature,
+    
+    /// The signature does not match the signed payload
+    #[error("signature is invalid")]
+    InvalidSignature,
+    
+    /// The consistency path does not reproduce the older tree's root
+    #[error("computed old root {computed} does not match expected old root {expected}")]
+    OldRootMismatch { expected: String, computed: String },
 }
 
 impl From<reqwest::Error> for TruthlinkedError {
     fn from(err: reqwest::Error) -> Self {
         // Don't leak internal details
-        if err.is_timeout() {
+        if crate::tls::is_pin_mismatch(&err) {
+            TruthlinkedError::CertificatePinMismatch
+        } else if err.is_timeout() {
             TruthlinkedError::Network("Request timeout".to_string())
         } else if err.is_connect() {
             TruthlinkedError::Network("Connection failed".to_string())
@@ -118,3 +349,38 @@ impl From<serde_json::Error> for TruthlinkedError {
 }
 
 pub type Result<T> = std::result::Result<T, TruthlinkedError>;
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use reqwest::header::HeaderValue;
+    
+    #[test]
+    fn test_rate_limit_headers_parsed() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("120"));
+        headers.insert("X-RateLimit-Remaining", HeaderValue::from_static("0"));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("60"));
+        
+        let info = RateLimitInfo::from_headers("slow down".to_string(), &headers);
+        assert_eq!(info.retry_after, Some(Duration::from_secs(120)));
+        assert_eq!(info.remaining, Some(0));
+        assert_eq!(info.reset_after, Some(Duration::from_secs(60)));
+        
+        let info = RateLimitInfo { retry_after: None, ..info };
+        assert_eq!(info.advised_delay(), Some(Duration::from_secs(60)));
+    }
+    
+    #[test]
+    fn test_retry_after_http_date() {
+        let mut headers = HeaderMap::new();
+        headers.insert(RETRY_AFTER, HeaderValue::from_static("Wed, 21 Oct 2015 07:28:00 GMT"));
+        headers.insert("X-RateLimit-Reset", HeaderValue::from_static("1445412480"));
+        
+        // Dates in the past mean "now"
+        let info = RateLimitInfo::from_headers(String::new(), &headers);
+        assert_eq!(info.retry_after, Some(Duration::ZERO));
+        assert_eq!(info.reset_after, Some(Duration::ZERO));
+        assert!(!RateLimitInfo::from("limited").has_hints());
+    }
+}
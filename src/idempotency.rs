@@ -0,0 +1,136 @@
+use crate::error::{Result, TruthlinkedError};
+use crate::types::WitnessSubmission;
+use rand::RngCore;
+use sha2::{Digest, Sha256};
+use std::fmt;
+
+/// Longest key accepted from callers
+const MAX_KEY_LEN: usize = 255;
+
+/// Value of the `Idempotency-Key` header sent with non-idempotent POSTs
+///
+/// The server answers a repeated key with the original response instead of
+/// processing the request again. The SDK generates one key per call and
+/// reuses it across retries; supply your own (e.g. with
+/// [`for_witness`](Self::for_witness)) to also deduplicate across calls,
+/// such as after a process restart.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::IdempotencyKey;
+///
+/// let key = IdempotencyKey::new("order-1234").unwrap();
+/// assert_eq!(key.as_str(), "order-1234");
+/// assert!(IdempotencyKey::new("has spaces").is_err());
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IdempotencyKey(String);
+
+impl IdempotencyKey {
+    /// Random 128-bit key, hex encoded
+    pub fn generate() -> Self {
+        let mut bytes = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut bytes);
+        Self(hex::encode(bytes))
+    }
+    
+    /// Caller-chosen key: 1 to 255 visible ASCII characters
+    pub fn new(key: impl Into<String>) -> Result<Self> {
+        let key = key.into();
+        if key.is_empty() || key.len() > MAX_KEY_LEN || !key.bytes().all(|b| b.is_ascii_graphic()) {
+            return Err(TruthlinkedError::InvalidRequest(format!("Invalid idempotency key: {:?}", key)));
+        }
+        Ok(Self(key))
+    }
+    
+    /// Key identifying `submission` by AF instance and sequence number
+    ///
+    /// Resubmitting the same event yields the same key, whatever process
+    /// sends it.
+    pub fn for_witness(submission: &WitnessSubmission) -> Self {
+        let mut hasher = Sha256::new();
+        hasher.update(submission.af_instance_id.as_bytes());
+        hasher.update(submission.af_sequence.to_be_bytes());
+        Self(format!("witness-{}", hex::encode(hasher.finalize())))
+    }
+    
+    /// The header value
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+    
+    /// Key for one part of a multi-request call, e.g. a replay chunk
+    ///
+    /// Keys that would exceed the length limit keep a prefix of this key and
+    /// end in a SHA-256 hash of the full key, so they stay distinct.
+    pub(crate) fn part(&self, part: impl fmt::Display) -> Self {
+        let key = format!("{}-{}", self.0, part);
+        if key.len() <= MAX_KEY_LEN {
+            return Self(key);
+        }
+        
+        let digest = hex::encode(Sha256::digest(key.as_bytes()));
+        let prefix = &self.0[..self.0.len().min(MAX_KEY_LEN - digest.len() - 1)];
+        Self(format!("{}-{}", prefix, digest))
+    }
+}
+
+impl fmt::Display for IdempotencyKey {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn submission(instance: &str, sequence: u64) -> WitnessSubmission {
+        WitnessSubmission {
+            af_event_hash: "00".repeat(32),
+            af_merkle_root: "00".repeat(32),
+            af_sequence: sequence,
+            af_instance_id: instance.to_string(),
+            oracle_time: 0,
+            af_signature: String::new(),
+        }
+    }
+    
+    #[test]
+    fn test_generated_keys_unique() {
+        let key = IdempotencyKey::generate();
+        assert_eq!(key.as_str().len(), 32);
+        assert_ne!(key, IdempotencyKey::generate());
+        assert!(IdempotencyKey::new(key.as_str()).is_ok());
+    }
+    
+    #[test]
+    fn test_new_validates() {
+        assert!(IdempotencyKey::new("").is_err());
+        assert!(IdempotencyKey::new("a\nb").is_err());
+        assert!(IdempotencyKey::new("k".repeat(256)).is_err());
+        assert!(IdempotencyKey::new("k".repeat(255)).is_ok());
+    }
+    
+    #[test]
+    fn test_witness_key_stable() {
+        let key = IdempotencyKey::for_witness(&submission("af-1", 7));
+        assert_eq!(key, IdempotencyKey::for_witness(&submission("af-1", 7)));
+        assert_ne!(key, IdempotencyKey::for_witness(&submission("af-1", 8)));
+        assert_ne!(key, IdempotencyKey::for_witness(&submission("af-2", 7)));
+    }
+    
+    #[test]
+    fn test_part_keys_within_limit() {
+        let short = IdempotencyKey::new("base").unwrap();
+        assert_eq!(short.part(3).as_str(), "base-3");
+        
+        let long = IdempotencyKey::new("k".repeat(MAX_KEY_LEN)).unwrap();
+        let first = long.part("10000-4194304-0");
+        let second = long.part("10000-4194304-10000");
+        assert_eq!(first.as_str().len(), MAX_KEY_LEN);
+        assert!(IdempotencyKey::new(first.as_str()).is_ok());
+        assert_ne!(first, second);
+        assert_eq!(first, long.part("10000-4194304-0"));
+    }
+}
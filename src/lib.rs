@@ -173,20 +173,48 @@
 //! - **Issues**: <https://github.com/truthlinked/sdk/issues>
 //! - **Email**: support@truthlinked.org
 
+mod adapter;
 mod builder;
 mod client;
+mod compliance;
 mod error;
+mod idempotency;
 mod license;
 mod logging;
+mod pagination;
+mod query;
+mod replay;
 mod retry;
+mod shadow;
 mod signing;
+mod tls;
+mod token;
 mod types;
 
+pub mod witness;
+
+#[cfg(any(feature = "axum", feature = "actix"))]
+pub mod middleware;
+
+pub use adapter::{IamAdapter, MalformedLine, ParsedLogs};
 pub use builder::ClientBuilder;
 pub use client::Client;
-pub use error::{TruthlinkedError, Result};
+pub use compliance::{
+    ComplianceFinding, ComplianceFramework, ComplianceQuery, ComplianceReport, ControlSection, ControlStatus,
+    EvidenceRef, ReportPeriod,
+};
+pub use error::{TruthlinkedError, RateLimitInfo, Result, VerificationError};
+pub use idempotency::IdempotencyKey;
 pub use logging::{LoggingConfig, LogLevel};
-pub use retry::RetryConfig;
+pub use pagination::{Page, PageStream, StreamCheckpoint};
+pub use query::{AuditLogQuery, ShadowDecisionQuery};
+pub use replay::{ReplayProgress, ReplayUpload};
+pub use retry::{Deadline, DecorrelatedJitter, FixedDelay, NoRetry, RetryConfig, RetryContext, RetryPolicy};
+pub use shadow::{IamDecision, PolicyEffect, PolicyRule, ShadowEvaluator, ShadowPolicy};
+pub use token::{
+    AfToken, ChannelBinding, NonceGenerator, TokenManager, TokenRequirements, TokenVerifier,
+    TLS_EXPORTER_LABEL,
+};
 pub use types::*;
 
 // Re-export for convenience
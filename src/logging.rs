@@ -239,30 +239,19 @@ impl RequestLogger {
                 // Simple credential redaction for common patterns
                 let mut result = text.to_string();
                 
-                // Redact sso_token values
-                if let Some(start) = result.find(r#""sso_token":""#) {
-                    let value_start = start + r#""sso_token":""#.len();
-                    if let Some(end) = result[value_start..].find('"') {
-                        let value_end = value_start + end;
-                        result.replace_range(value_start..value_end, "***");
-                    }
-                }
-                
-                // Redact af_token values
-                if let Some(start) = result.find(r#""af_token":""#) {
-                    let value_start = start + r#""af_token":""#.len();
-                    if let Some(end) = result[value_start..].find('"') {
-                        let value_end = value_start + end;
-                        result.replace_range(value_start..value_end, "***");
-                    }
-                }
-                
-                // Redact license_key values
-                if let Some(start) = result.find(r#""license_key":""#) {
-                    let value_start = start + r#""license_key":""#.len();
-                    if let Some(end) = result[value_start..].find('"') {
-                        let value_end = value_start + end;
-                        result.replace_range(value_start..value_end, "***");
+                // Redact every value of credential fields (batch bodies repeat them)
+                for pattern in [
+                    r#""sso_token":""#,
+                    r#""af_token":""#,
+                    r#""license_key":""#,
+                    r#""token":""#,
+                ] {
+                    let mut search_from = 0;
+                    while let Some(start) = result[search_from..].find(pattern) {
+                        let value_start = search_from + start + pattern.len();
+                        let Some(end) = result[value_start..].find('"') else { break };
+                        result.replace_range(value_start..value_start + end, "***");
+                        search_from = value_start + "***".len();
                     }
                 }
                 
@@ -335,4 +324,16 @@ mod tests {
         assert!(redacted.contains(r#""sso_token":"***""#));
         assert!(redacted.contains(r#""other":"safe""#));
     }
+    
+    #[test]
+    fn test_body_redaction_repeated_fields() {
+        let logger = RequestLogger::new(LoggingConfig::default());
+        
+        let body = r#"{"requests":[{"token":"first","permission":"a"},{"token":"second","permission":"b"}]}"#;
+        let redacted = logger.redact_body(body.as_bytes());
+        
+        assert!(!redacted.contains("first"));
+        assert!(!redacted.contains("second"));
+        assert!(redacted.contains(r#""permission":"b""#));
+    }
 }
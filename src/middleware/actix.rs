@@ -0,0 +1,190 @@
+use super::{AfPrincipal, AuthConfig, AuthRejection, FailurePolicy, TokenValidator};
+use actix_web::body::EitherBody;
+use actix_web::dev::{forward_ready, Payload, Service, ServiceRequest, ServiceResponse, Transform};
+use actix_web::http::{header, StatusCode};
+use actix_web::{FromRequest, HttpMessage, HttpRequest, HttpResponse, ResponseError};
+use std::future::{ready, Future, Ready};
+use std::pin::Pin;
+use std::rc::Rc;
+use std::sync::Arc;
+
+/// Shared AF token validation settings for actix-web
+///
+/// Register once with `App::app_data`; the [`AfPrincipal`] extractor and the
+/// [`RequireScope`] middleware look it up from there.
+///
+/// # Example
+/// ```rust,no_run
+/// use actix_web::{web, App};
+/// use std::sync::Arc;
+/// use truthlinked_sdk::middleware::{AfPrincipal, RequireScope, TruthlinkedAuth};
+/// use truthlinked_sdk::Client;
+///
+/// async fn list_users(principal: AfPrincipal) -> String {
+///     format!("Hello {:?}", principal.subject)
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(Client::new("https://api.truthlinked.org", "key")?);
+/// let auth = TruthlinkedAuth::new(client);
+///
+/// let app = App::new().app_data(auth).service(
+///     web::resource("/users")
+///         .wrap(RequireScope::new("read:users"))
+///         .route(web::get().to(list_users)),
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TruthlinkedAuth {
+    config: AuthConfig,
+}
+
+impl TruthlinkedAuth {
+    /// Authenticate with `validator`, e.g. `Arc<Client>` or `Arc<TokenVerifier>`
+    pub fn new<V: TokenValidator>(validator: Arc<V>) -> Self {
+        Self {
+            config: AuthConfig::new(validator),
+        }
+    }
+    
+    /// What to do on [`TruthlinkedError::Network`](crate::TruthlinkedError::Network)
+    /// (default: [`FailurePolicy::FailClosed`])
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.config.failure_policy = policy;
+        self
+    }
+}
+
+/// Authenticate `request`, reusing a principal the middleware already set
+async fn authenticate(request: &HttpRequest, required_scope: &[String]) -> Result<AfPrincipal, AuthRejection> {
+    let Some(auth) = request.app_data::<TruthlinkedAuth>() else {
+        tracing::error!("TruthlinkedAuth is not registered with App::app_data");
+        return Err(AuthRejection::Internal);
+    };
+    let mut config = auth.config.clone();
+    config.required_scope = required_scope.to_vec();
+    
+    let existing = request.extensions().get::<AfPrincipal>().cloned();
+    if let Some(principal) = existing {
+        config.check_scope(&principal)?;
+        return Ok(principal);
+    }
+    
+    let authorization = request
+        .headers()
+        .get(header::AUTHORIZATION)
+        .and_then(|value| value.to_str().ok());
+    let principal = config.authorize(authorization).await?;
+    
+    request.extensions_mut().insert(principal.clone());
+    Ok(principal)
+}
+
+/// Extracts the authenticated caller, validating the bearer token if no
+/// [`RequireScope`] middleware has already done so
+impl FromRequest for AfPrincipal {
+    type Error = AuthRejection;
+    type Future = Pin<Box<dyn Future<Output = Result<Self, Self::Error>>>>;
+    
+    fn from_request(request: &HttpRequest, _payload: &mut Payload) -> Self::Future {
+        let request = request.clone();
+        Box::pin(async move { authenticate(&request, &[]).await })
+    }
+}
+
+impl ResponseError for AuthRejection {
+    fn status_code(&self) -> StatusCode {
+        StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR)
+    }
+    
+    fn error_response(&self) -> HttpResponse {
+        let mut response = HttpResponse::build(self.status_code());
+        if let Some(challenge) = self.challenge() {
+            response.insert_header((header::WWW_AUTHENTICATE, challenge));
+        }
+        response.json(serde_json::json!({ "error": self.error_code() }))
+    }
+}
+
+/// Middleware that rejects requests whose AF token lacks a scope
+///
+/// actix-web guards are synchronous and answer 404 when they do not match,
+/// so scope checks run as middleware instead: wrap a scope or resource with
+/// `RequireScope` to validate the bearer token and require the scope before
+/// any handler runs. The resulting [`AfPrincipal`] is available to handlers
+/// without a second validation.
+///
+/// Requires [`TruthlinkedAuth`] in the app data.
+#[derive(Debug, Clone)]
+pub struct RequireScope {
+    scope: Rc<Vec<String>>,
+}
+
+impl RequireScope {
+    /// Require `scope`
+    pub fn new(scope: impl Into<String>) -> Self {
+        Self {
+            scope: Rc::new(vec![scope.into()]),
+        }
+    }
+    
+    /// Require `scope` as well
+    pub fn and(self, scope: impl Into<String>) -> Self {
+        let mut required = (*self.scope).clone();
+        required.push(scope.into());
+        Self {
+            scope: Rc::new(required),
+        }
+    }
+}
+
+impl<S, B> Transform<S, ServiceRequest> for RequireScope
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Transform = RequireScopeMiddleware<S>;
+    type InitError = ();
+    type Future = Ready<Result<Self::Transform, Self::InitError>>;
+    
+    fn new_transform(&self, service: S) -> Self::Future {
+        ready(Ok(RequireScopeMiddleware {
+            service: Rc::new(service),
+            scope: self.scope.clone(),
+        }))
+    }
+}
+
+/// Service produced by [`RequireScope`]
+pub struct RequireScopeMiddleware<S> {
+    service: Rc<S>,
+    scope: Rc<Vec<String>>,
+}
+
+impl<S, B> Service<ServiceRequest> for RequireScopeMiddleware<S>
+where
+    S: Service<ServiceRequest, Response = ServiceResponse<B>, Error = actix_web::Error> + 'static,
+    B: 'static,
+{
+    type Response = ServiceResponse<EitherBody<B>>;
+    type Error = actix_web::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>>>>;
+    
+    forward_ready!(service);
+    
+    fn call(&self, request: ServiceRequest) -> Self::Future {
+        let service = self.service.clone();
+        let scope = self.scope.clone();
+        
+        Box::pin(async move {
+            match authenticate(request.request(), &scope).await {
+                Ok(_) => service.call(request).await.map(ServiceResponse::map_into_left_body),
+                Err(rejection) => Ok(request.error_response(rejection).map_into_right_body()),
+            }
+        })
+    }
+}
@@ -0,0 +1,160 @@
+use super::{AfPrincipal, AuthConfig, AuthRejection, FailurePolicy, TokenValidator};
+use axum::body::Body;
+use axum::extract::FromRequestParts;
+use axum::http::{header, request::Parts, HeaderValue, Request, Response, StatusCode};
+use axum::response::IntoResponse;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+use tower_layer::Layer;
+use tower_service::Service;
+
+/// Tower layer that authenticates requests by their bearer AF token
+///
+/// Valid requests reach the inner service with an [`AfPrincipal`] in their
+/// extensions; everything else is answered by the layer as described in the
+/// [module docs](super).
+///
+/// Required scopes are per layer, so apply a scoped copy to individual
+/// routes with [`require_scope`](Self::require_scope).
+///
+/// # Example
+/// ```rust,no_run
+/// use axum::{routing::get, Router};
+/// use std::sync::Arc;
+/// use truthlinked_sdk::middleware::{AfPrincipal, TruthlinkedAuthLayer};
+/// use truthlinked_sdk::Client;
+///
+/// async fn list_users(principal: AfPrincipal) -> String {
+///     format!("Hello {:?}", principal.subject)
+/// }
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(Client::new("https://api.truthlinked.org", "key")?);
+/// let auth = TruthlinkedAuthLayer::new(client);
+///
+/// let app: Router = Router::new()
+///     .route("/users", get(list_users).route_layer(auth.require_scope("read:users")))
+///     .layer(auth);
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Clone)]
+pub struct TruthlinkedAuthLayer {
+    config: AuthConfig,
+}
+
+impl TruthlinkedAuthLayer {
+    /// Authenticate with `validator`, e.g. `Arc<Client>` or `Arc<TokenVerifier>`
+    pub fn new<V: TokenValidator>(validator: Arc<V>) -> Self {
+        Self {
+            config: AuthConfig::new(validator),
+        }
+    }
+    
+    /// A copy of this layer that also requires `scope`
+    pub fn require_scope(&self, scope: impl Into<String>) -> Self {
+        let mut layer = self.clone();
+        layer.config.required_scope.push(scope.into());
+        layer
+    }
+    
+    /// What to do when the validation service is unreachable
+    /// (default: [`FailurePolicy::FailClosed`])
+    pub fn failure_policy(mut self, policy: FailurePolicy) -> Self {
+        self.config.failure_policy = policy;
+        self
+    }
+}
+
+impl<S> Layer<S> for TruthlinkedAuthLayer {
+    type Service = TruthlinkedAuthService<S>;
+    
+    fn layer(&self, inner: S) -> Self::Service {
+        TruthlinkedAuthService {
+            inner,
+            config: self.config.clone(),
+        }
+    }
+}
+
+/// Service produced by [`TruthlinkedAuthLayer`]
+#[derive(Clone)]
+pub struct TruthlinkedAuthService<S> {
+    inner: S,
+    config: AuthConfig,
+}
+
+impl<S, B> Service<Request<B>> for TruthlinkedAuthService<S>
+where
+    S: Service<Request<B>, Response = Response<Body>> + Clone + Send + 'static,
+    S::Future: Send + 'static,
+    B: Send + 'static,
+{
+    type Response = Response<Body>;
+    type Error = S::Error;
+    type Future = Pin<Box<dyn Future<Output = Result<Self::Response, Self::Error>> + Send>>;
+    
+    fn poll_ready(&mut self, cx: &mut Context<'_>) -> Poll<Result<(), Self::Error>> {
+        self.inner.poll_ready(cx)
+    }
+    
+    fn call(&mut self, mut request: Request<B>) -> Self::Future {
+        // Use the instance that was driven to readiness
+        let clone = self.inner.clone();
+        let mut inner = std::mem::replace(&mut self.inner, clone);
+        let config = self.config.clone();
+        
+        Box::pin(async move {
+            // An outer layer may already have authenticated the request
+            let result = match request.extensions().get::<AfPrincipal>() {
+                Some(principal) => config.check_scope(principal).map(|_| None),
+                None => {
+                    let authorization = request
+                        .headers()
+                        .get(header::AUTHORIZATION)
+                        .and_then(|value| value.to_str().ok());
+                    config.authorize(authorization).await.map(Some)
+                }
+            };
+            
+            match result {
+                Ok(Some(principal)) => {
+                    request.extensions_mut().insert(principal);
+                    inner.call(request).await
+                }
+                Ok(None) => inner.call(request).await,
+                Err(rejection) => Ok(rejection.into_response()),
+            }
+        })
+    }
+}
+
+impl IntoResponse for AuthRejection {
+    fn into_response(self) -> Response<Body> {
+        let status = StatusCode::from_u16(self.status()).unwrap_or(StatusCode::INTERNAL_SERVER_ERROR);
+        let body = serde_json::json!({ "error": self.error_code() }).to_string();
+        
+        let mut response = (status, [(header::CONTENT_TYPE, "application/json")], body).into_response();
+        if let Some(challenge) = self.challenge().and_then(|c| HeaderValue::from_str(&c).ok()) {
+            response.headers_mut().insert(header::WWW_AUTHENTICATE, challenge);
+        }
+        response
+    }
+}
+
+/// Extracts the principal inserted by [`TruthlinkedAuthLayer`]
+///
+/// Rejects with 401 on routes the layer does not cover.
+impl<S: Send + Sync> FromRequestParts<S> for AfPrincipal {
+    type Rejection = AuthRejection;
+    
+    async fn from_request_parts(parts: &mut Parts, _state: &S) -> Result<Self, Self::Rejection> {
+        parts
+            .extensions
+            .get::<AfPrincipal>()
+            .cloned()
+            .ok_or(AuthRejection::MissingToken)
+    }
+}
@@ -0,0 +1,278 @@
+//! Server-side authorization for AF tokens
+//!
+//! Web framework integrations that authenticate incoming requests by their
+//! bearer AF token, enabled by cargo features:
+//!
+//! | Feature | Integration |
+//! |---------|-------------|
+//! | `axum` | [`TruthlinkedAuthLayer`], a tower layer, and an [`AfPrincipal`] extractor |
+//! | `actix` | [`TruthlinkedAuth`] app data, an [`AfPrincipal`] extractor and [`RequireScope`] middleware |
+//!
+//! Every integration follows the same steps:
+//! 1. Extract the token from the `Authorization: Bearer` header
+//! 2. Validate it with a [`TokenValidator`]: a [`Client`] (one
+//!    `validate_bearer_token` call per request) or a [`TokenVerifier`] (local
+//!    signature check against cached issuer keys)
+//! 3. Check the route's required scope
+//! 4. Make the resulting [`AfPrincipal`] available to handlers
+//!
+//! # Rejections
+//!
+//! | Outcome | Status |
+//! |---------|--------|
+//! | No bearer token, invalid or expired token | 401 |
+//! | Token lacks a required scope | 403 |
+//! | Validation service unreachable | 503, or admitted to routes without a required scope under [`FailurePolicy::FailOpen`] |
+//! | Any other SDK error | 500 |
+//!
+//! 401 and 403 responses carry an RFC 6750 `WWW-Authenticate` header.
+
+#[cfg(feature = "actix")]
+mod actix;
+#[cfg(feature = "axum")]
+mod layer;
+
+#[cfg(feature = "actix")]
+pub use actix::{RequireScope, RequireScopeMiddleware, TruthlinkedAuth};
+#[cfg(feature = "axum")]
+pub use layer::{TruthlinkedAuthLayer, TruthlinkedAuthService};
+
+use crate::client::Client;
+use crate::error::{Result, TruthlinkedError};
+use crate::token::{TokenRequirements, TokenVerifier};
+use crate::types::ValidateResponse;
+use std::future::Future;
+use std::pin::Pin;
+use std::sync::Arc;
+
+/// Future returned by [`TokenValidator::validate`]
+pub type ValidateFuture<'a> = Pin<Box<dyn Future<Output = Result<ValidateResponse>> + Send + 'a>>;
+
+/// Validates bearer AF tokens for the middleware
+///
+/// Implemented for [`Client`] and [`TokenVerifier`]; implement it to plug
+/// in your own cache.
+pub trait TokenValidator: Send + Sync + 'static {
+    /// Validate `token`, as presented in the `Authorization` header
+    fn validate<'a>(&'a self, token: &'a str) -> ValidateFuture<'a>;
+}
+
+impl TokenValidator for Client {
+    fn validate<'a>(&'a self, token: &'a str) -> ValidateFuture<'a> {
+        Box::pin(self.validate_bearer_token(token))
+    }
+}
+
+impl TokenValidator for TokenVerifier {
+    fn validate<'a>(&'a self, token: &'a str) -> ValidateFuture<'a> {
+        Box::pin(async move { self.verify(token, &TokenRequirements::new()).await })
+    }
+}
+
+/// What to do when the validation service cannot be reached
+///
+/// Applies to [`TruthlinkedError::Network`] only; every other error is
+/// rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum FailurePolicy {
+    /// Reject the request with 503 (default)
+    #[default]
+    FailClosed,
+    /// Admit the request with a [degraded](AfPrincipal::degraded) principal
+    ///
+    /// Routes with a required scope still reject such requests with 503.
+    FailOpen,
+}
+
+/// The authenticated caller of a request
+///
+/// Built from the [`ValidateResponse`] of the caller's AF token.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AfPrincipal {
+    /// Subject the token was issued to
+    pub subject: Option<String>,
+    /// Scope granted to the token
+    pub scope: Vec<String>,
+    /// Admitted under [`FailurePolicy::FailOpen`] without validation
+    ///
+    /// A degraded principal has no subject and no scope.
+    pub degraded: bool,
+}
+
+impl AfPrincipal {
+    /// Whether the token was granted `scope`
+    pub fn has_scope(&self, scope: &str) -> bool {
+        self.scope.iter().any(|granted| granted == scope)
+    }
+    
+    fn degraded() -> Self {
+        Self {
+            subject: None,
+            scope: Vec::new(),
+            degraded: true,
+        }
+    }
+}
+
+impl From<ValidateResponse> for AfPrincipal {
+    fn from(response: ValidateResponse) -> Self {
+        Self {
+            subject: response.subject,
+            scope: response.scope.unwrap_or_default(),
+            degraded: false,
+        }
+    }
+}
+
+/// Why a request was refused
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AuthRejection {
+    /// No `Authorization: Bearer` header
+    MissingToken,
+    /// The token is invalid, expired or was rejected by the server
+    InvalidToken,
+    /// The token lacks a scope the route requires
+    InsufficientScope(Vec<String>),
+    /// The validation service could not be reached
+    Unavailable,
+    /// Validation failed for another reason
+    Internal,
+}
+
+impl AuthRejection {
+    /// HTTP status code for the rejection
+    pub fn status(&self) -> u16 {
+        match self {
+            AuthRejection::MissingToken | AuthRejection::InvalidToken => 401,
+            AuthRejection::InsufficientScope(_) => 403,
+            AuthRejection::Unavailable => 503,
+            AuthRejection::Internal => 500,
+        }
+    }
+    
+    /// RFC 6750 `WWW-Authenticate` challenge, for 401 and 403 rejections
+    pub fn challenge(&self) -> Option<String> {
+        match self {
+            AuthRejection::MissingToken => Some("Bearer".to_string()),
+            AuthRejection::InvalidToken => Some("Bearer error=\"invalid_token\"".to_string()),
+            AuthRejection::InsufficientScope(scope) => Some(format!(
+                "Bearer error=\"insufficient_scope\", scope=\"{}\"",
+                scope.join(" ")
+            )),
+            AuthRejection::Unavailable | AuthRejection::Internal => None,
+        }
+    }
+    
+    /// Short machine-readable reason, used as the response body
+    pub fn error_code(&self) -> &'static str {
+        match self {
+            AuthRejection::MissingToken => "missing_token",
+            AuthRejection::InvalidToken => "invalid_token",
+            AuthRejection::InsufficientScope(_) => "insufficient_scope",
+            AuthRejection::Unavailable => "temporarily_unavailable",
+            AuthRejection::Internal => "server_error",
+        }
+    }
+}
+
+impl std::fmt::Display for AuthRejection {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.error_code())
+    }
+}
+
+impl From<&TruthlinkedError> for AuthRejection {
+    fn from(error: &TruthlinkedError) -> Self {
+        match error {
+            TruthlinkedError::Unauthorized | TruthlinkedError::InvalidRequest(_) => AuthRejection::InvalidToken,
+            TruthlinkedError::Forbidden => AuthRejection::InsufficientScope(Vec::new()),
+            TruthlinkedError::Network(_)
+            | TruthlinkedError::ServerError
+            | TruthlinkedError::ServiceUnavailable(_)
+            | TruthlinkedError::RateLimitExceeded(_) => AuthRejection::Unavailable,
+            _ => AuthRejection::Internal,
+        }
+    }
+}
+
+/// Settings shared by every framework integration
+#[derive(Clone)]
+pub(crate) struct AuthConfig {
+    pub(crate) validator: Arc<dyn TokenValidator>,
+    pub(crate) required_scope: Vec<String>,
+    pub(crate) failure_policy: FailurePolicy,
+}
+
+impl AuthConfig {
+    pub(crate) fn new(validator: Arc<dyn TokenValidator>) -> Self {
+        Self {
+            validator,
+            required_scope: Vec::new(),
+            failure_policy: FailurePolicy::default(),
+        }
+    }
+    
+    /// Authenticate a request from its `Authorization` header value
+    pub(crate) async fn authorize(&self, authorization: Option<&str>) -> std::result::Result<AfPrincipal, AuthRejection> {
+        let token = authorization
+            .and_then(bearer_token)
+            .ok_or(AuthRejection::MissingToken)?;
+        
+        let principal = match self.validator.validate(token).await {
+            Ok(response) if response.valid => AfPrincipal::from(response),
+            Ok(_) => return Err(AuthRejection::InvalidToken),
+            Err(TruthlinkedError::Network(e)) if self.failure_policy == FailurePolicy::FailOpen => {
+                tracing::warn!(error = %e, "AF token validation unreachable, failing open");
+                AfPrincipal::degraded()
+            }
+            Err(e) => {
+                tracing::warn!(error = %e, "AF token validation failed");
+                return Err(AuthRejection::from(&e));
+            }
+        };
+        
+        self.check_scope(&principal)?;
+        Ok(principal)
+    }
+    
+    /// Check the required scope against an authenticated principal
+    pub(crate) fn check_scope(&self, principal: &AfPrincipal) -> std::result::Result<(), AuthRejection> {
+        if self.required_scope.iter().all(|scope| principal.has_scope(scope)) {
+            Ok(())
+        } else if principal.degraded {
+            // An unvalidated caller can never prove a scope
+            Err(AuthRejection::Unavailable)
+        } else {
+            Err(AuthRejection::InsufficientScope(self.required_scope.clone()))
+        }
+    }
+}
+
+/// Extract the token from an `Authorization: Bearer <token>` header value
+fn bearer_token(header: &str) -> Option<&str> {
+    let (scheme, token) = header.trim().split_once(' ')?;
+    let token = token.trim();
+    (scheme.eq_ignore_ascii_case("bearer") && !token.is_empty()).then_some(token)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_bearer_token() {
+        assert_eq!(bearer_token("Bearer abc.def"), Some("abc.def"));
+        assert_eq!(bearer_token("bearer  abc "), Some("abc"));
+        assert_eq!(bearer_token("Basic abc"), None);
+        assert_eq!(bearer_token("Bearer "), None);
+        assert_eq!(bearer_token("abc"), None);
+    }
+    
+    #[test]
+    fn test_error_mapping() {
+        assert_eq!(AuthRejection::from(&TruthlinkedError::Unauthorized).status(), 401);
+        assert_eq!(AuthRejection::from(&TruthlinkedError::Forbidden).status(), 403);
+        assert_eq!(AuthRejection::from(&TruthlinkedError::Network("down".into())).status(), 503);
+        assert_eq!(AuthRejection::from(&TruthlinkedError::SerializationError).status(), 500);
+    }
+}
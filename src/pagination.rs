@@ -0,0 +1,273 @@
+use crate::error::{Result, TruthlinkedError};
+use futures_core::Stream;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::future::Future;
+use std::pin::Pin;
+use std::task::{Context, Poll};
+use std::time::Duration;
+
+/// Default delay before retrying a rate-limited page fetch
+const DEFAULT_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(1);
+
+/// Upper bound for the rate limit backoff
+const MAX_RATE_LIMIT_BACKOFF: Duration = Duration::from_secs(60);
+
+/// Default number of rate-limited retries per page
+const DEFAULT_RATE_LIMIT_RETRIES: u32 = 5;
+
+/// Default pause before polling again when a followed stream has nothing new
+const DEFAULT_FOLLOW_IDLE_DELAY: Duration = Duration::from_secs(1);
+
+/// One page of a cursor-paginated listing
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Page<T> {
+    /// Items on this page
+    #[serde(alias = "logs", alias = "decisions")]
+    pub items: Vec<T>,
+    /// Cursor for the following page; `None` on the last page
+    #[serde(default)]
+    pub next_cursor: Option<String>,
+}
+
+/// Position in a paginated listing, saved to resume a [`PageStream`]
+///
+/// Identifies the page by the cursor it was fetched with and counts the
+/// items already consumed from it, so a resumed stream neither repeats nor
+/// skips items as long as the server's pages are stable.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct StreamCheckpoint {
+    /// Cursor of the page being consumed; `None` for the first page
+    pub cursor: Option<String>,
+    /// Items of that page already yielded
+    pub offset: usize,
+}
+
+pub(crate) type PageFuture<'a, T> = Pin<Box<dyn Future<Output = Result<Page<T>>> + Send + 'a>>;
+/// Fetches the page at a cursor, given how many of its items were already
+/// yielded (nonzero when resuming or re-polling the tail page)
+pub(crate) type PageFetcher<'a, T> = Box<dyn FnMut(Option<String>, usize) -> PageFuture<'a, T> + Send + 'a>;
+
+enum State<'a, T> {
+    Idle,
+    Fetching(PageFuture<'a, T>),
+    Sleeping(Pin<Box<tokio::time::Sleep>>),
+    Done,
+}
+
+/// A lazily fetched, cursor-paginated listing as a [`Stream`]
+///
+/// Pages are requested only as items are consumed. A page fetch that hits
+/// the rate limit is retried after the server's `Retry-After` interval, or
+/// with exponential backoff (1s doubling to 60s) if none is sent; after 5
+/// retries by default the error is yielded. If the client already retries
+/// rate limits ([`ClientBuilder::honor_retry_after`](crate::ClientBuilder::honor_retry_after)),
+/// the stream leaves them to it and does not retry again. Any error ends the
+/// stream; resume it from [`checkpoint`](Self::checkpoint).
+///
+/// A followed stream (see [`Client::follow_shadow_decisions`](crate::Client::follow_shadow_decisions))
+/// does not end at the last page but keeps polling it for new items.
+/// Delivery is at-least-once: an item yielded after the last saved
+/// checkpoint is yielded again on resume.
+///
+/// # Example
+/// ```rust,no_run
+/// use futures_util::StreamExt;
+/// use truthlinked_sdk::{AuditLogQuery, Client};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("https://api.truthlinked.org", "key")?;
+/// let mut logs = client.audit_log_stream(AuditLogQuery::new().subject("alice"));
+/// while let Some(log) = logs.next().await {
+///     let log = log?;
+///     println!("{}: {}", log.timestamp, log.action);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct PageStream<'a, T> {
+    fetch: PageFetcher<'a, T>,
+    state: State<'a, T>,
+    buffer: VecDeque<T>,
+    /// Position of the next item to yield
+    checkpoint: StreamCheckpoint,
+    /// Cursor being fetched, or `None` once the last page is buffered
+    pending_cursor: Option<Option<String>>,
+    rate_limit_backoff: Duration,
+    rate_limit_retries: u32,
+    retries_left: u32,
+    /// The client's retry executor already retries rate limits
+    client_retries_rate_limits: bool,
+    /// Keep polling the last page instead of ending
+    follow: bool,
+    idle_delay: Duration,
+}
+
+impl<'a, T> PageStream<'a, T> {
+    pub(crate) fn new(fetch: PageFetcher<'a, T>) -> Self {
+        Self {
+            fetch,
+            state: State::Idle,
+            buffer: VecDeque::new(),
+            checkpoint: StreamCheckpoint::default(),
+            pending_cursor: Some(None),
+            rate_limit_backoff: DEFAULT_RATE_LIMIT_BACKOFF,
+            rate_limit_retries: DEFAULT_RATE_LIMIT_RETRIES,
+            retries_left: DEFAULT_RATE_LIMIT_RETRIES,
+            client_retries_rate_limits: false,
+            follow: false,
+            idle_delay: DEFAULT_FOLLOW_IDLE_DELAY,
+        }
+    }
+    
+    /// Keep polling the last page for new items instead of ending
+    pub(crate) fn follow(mut self) -> Self {
+        self.follow = true;
+        self
+    }
+    
+    /// Leave rate-limited fetches to the client's retries when it has them
+    pub(crate) fn client_retries_rate_limits(mut self, enabled: bool) -> Self {
+        self.client_retries_rate_limits = enabled;
+        self
+    }
+    
+    /// Pause before polling again when a followed stream has nothing new
+    /// (default: 1s)
+    ///
+    /// Applies between long-poll requests that return no new items; has no
+    /// effect on streams that are not followed.
+    pub fn idle_delay(mut self, delay: Duration) -> Self {
+        self.idle_delay = delay;
+        self
+    }
+    
+    /// Start from a saved checkpoint instead of the first page
+    pub fn resume_from(mut self, checkpoint: StreamCheckpoint) -> Self {
+        self.pending_cursor = Some(checkpoint.cursor.clone());
+        self.checkpoint = checkpoint;
+        self
+    }
+    
+    /// Initial delay before retrying a rate-limited fetch (default: 1s)
+    ///
+    /// Unused when the client honors `Retry-After` itself.
+    pub fn rate_limit_backoff(mut self, delay: Duration) -> Self {
+        self.rate_limit_backoff = delay;
+        self
+    }
+    
+    /// Retries of a rate-limited fetch before giving up (default: 5)
+    ///
+    /// Unused when the client honors `Retry-After` itself.
+    pub fn rate_limit_retries(mut self, retries: u32) -> Self {
+        self.rate_limit_retries = retries;
+        self.retries_left = retries;
+        self
+    }
+    
+    /// Position to resume from after the items yielded so far
+    pub fn checkpoint(&self) -> StreamCheckpoint {
+        self.checkpoint.clone()
+    }
+    
+    fn start_fetch(&mut self) {
+        match self.pending_cursor.clone() {
+            Some(cursor) => {
+                let seen = if cursor == self.checkpoint.cursor { self.checkpoint.offset } else { 0 };
+                self.state = State::Fetching((self.fetch)(cursor, seen));
+            }
+            None => self.state = State::Done,
+        }
+    }
+    
+    fn accept_page(&mut self, page: Page<T>) {
+        let cursor = self.pending_cursor.take().flatten();
+        
+        // Resuming mid-page: drop the items consumed before the checkpoint
+        let skip = if cursor == self.checkpoint.cursor { self.checkpoint.offset } else { 0 };
+        self.checkpoint = StreamCheckpoint { cursor, offset: skip };
+        self.buffer = page.items.into_iter().skip(skip).collect();
+        
+        self.pending_cursor = match (page.next_cursor, self.follow) {
+            (Some(next), _) => Some(Some(next)),
+            // Following: poll the tail page again, skipping what was seen
+            (None, true) => Some(self.checkpoint.cursor.clone()),
+            (None, false) => None,
+        };
+        self.retries_left = self.rate_limit_retries;
+        self.state = if self.follow && self.buffer.is_empty() {
+            State::Sleeping(Box::pin(tokio::time::sleep(self.idle_delay)))
+        } else {
+            State::Idle
+        };
+    }
+    
+    fn backoff_delay(&self, advised: Option<Duration>) -> Duration {
+        let attempt = self.rate_limit_retries - self.retries_left;
+        let max = MAX_RATE_LIMIT_BACKOFF.max(self.rate_limit_backoff);
+        advised
+            .unwrap_or_else(|| self.rate_limit_backoff.saturating_mul(2u32.saturating_pow(attempt)))
+            .min(max)
+    }
+}
+
+impl<T: Unpin> Stream for PageStream<'_, T> {
+    type Item = Result<T>;
+    
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+        let this = self.get_mut();
+        
+        loop {
+            if let Some(item) = this.buffer.pop_front() {
+                this.checkpoint.offset += 1;
+                if this.buffer.is_empty() {
+                    // Page consumed: resume from the next one
+                    if let Some(Some(next)) = &this.pending_cursor {
+                        if this.checkpoint.cursor.as_ref() != Some(next) {
+                            this.checkpoint = StreamCheckpoint { cursor: Some(next.clone()), offset: 0 };
+                        }
+                    }
+                }
+                return Poll::Ready(Some(Ok(item)));
+            }
+            
+            match &mut this.state {
+                State::Done => return Poll::Ready(None),
+                State::Idle => this.start_fetch(),
+                State::Sleeping(sleep) => {
+                    if sleep.as_mut().poll(cx).is_pending() {
+                        return Poll::Pending;
+                    }
+                    this.start_fetch();
+                }
+                State::Fetching(fetch) => match fetch.as_mut().poll(cx) {
+                    Poll::Pending => return Poll::Pending,
+                    Poll::Ready(Ok(page)) => this.accept_page(page),
+                    Poll::Ready(Err(TruthlinkedError::RateLimitExceeded(info)))
+                        if this.retries_left > 0 && !this.client_retries_rate_limits =>
+                    {
+                        let delay = this.backoff_delay(info.advised_delay());
+                        this.retries_left -= 1;
+                        tracing::debug!(delay_ms = delay.as_millis() as u64, "Page fetch rate limited, backing off");
+                        this.state = State::Sleeping(Box::pin(tokio::time::sleep(delay)));
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.state = State::Done;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                },
+            }
+        }
+    }
+}
+
+impl<T> std::fmt::Debug for PageStream<'_, T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("PageStream")
+            .field("checkpoint", &self.checkpoint)
+            .field("buffered", &self.buffer.len())
+            .finish_non_exhaustive()
+    }
+}
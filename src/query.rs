@@ -0,0 +1,247 @@
+use crate::types::DivergenceDirection;
+use std::time::Duration;
+
+/// Default long-poll wait for followed shadow decision streams
+const DEFAULT_FOLLOW_WAIT: Duration = Duration::from_secs(20);
+
+/// Filters for listing audit logs
+///
+/// All filters are optional and combine with AND. Times are Unix seconds,
+/// matching [`AuditLog::timestamp`](crate::AuditLog::timestamp).
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::AuditLogQuery;
+///
+/// let query = AuditLogQuery::new()
+///     .since(1_700_000_000)
+///     .subject("alice")
+///     .result("denied")
+///     .page_size(500);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct AuditLogQuery {
+    since: Option<u64>,
+    until: Option<u64>,
+    subject: Option<String>,
+    event_type: Option<String>,
+    action: Option<String>,
+    result: Option<String>,
+    page_size: Option<u32>,
+}
+
+impl AuditLogQuery {
+    /// Query matching every audit log
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Only logs at or after `timestamp`
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.since = Some(timestamp);
+        self
+    }
+    
+    /// Only logs before `timestamp`
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.until = Some(timestamp);
+        self
+    }
+    
+    /// Only logs for `subject`
+    pub fn subject(mut self, subject: impl Into<String>) -> Self {
+        self.subject = Some(subject.into());
+        self
+    }
+    
+    /// Only logs of `event_type`
+    pub fn event_type(mut self, event_type: impl Into<String>) -> Self {
+        self.event_type = Some(event_type.into());
+        self
+    }
+    
+    /// Only logs for `action`
+    pub fn action(mut self, action: impl Into<String>) -> Self {
+        self.action = Some(action.into());
+        self
+    }
+    
+    /// Only logs with `result`, e.g. `"allowed"` or `"denied"`
+    pub fn result(mut self, result: impl Into<String>) -> Self {
+        self.result = Some(result.into());
+        self
+    }
+    
+    /// Logs per page (server default if unset)
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+    
+    /// Query string parameters, in a stable order
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(since) = self.since {
+            params.push(("since", since.to_string()));
+        }
+        if let Some(until) = self.until {
+            params.push(("until", until.to_string()));
+        }
+        for (name, value) in [
+            ("subject", &self.subject),
+            ("event_type", &self.event_type),
+            ("action", &self.action),
+            ("result", &self.result),
+        ] {
+            if let Some(value) = value {
+                params.push((name, value.clone()));
+            }
+        }
+        if let Some(page_size) = self.page_size {
+            params.push(("limit", page_size.to_string()));
+        }
+        params
+    }
+}
+
+/// Filters for listing shadow decisions
+///
+/// All filters are optional and combine with AND. Times are Unix seconds.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::{DivergenceDirection, ShadowDecisionQuery};
+///
+/// let query = ShadowDecisionQuery::new()
+///     .breach_prevented(true)
+///     .direction(DivergenceDirection::IamAllowedAfDenied);
+/// ```
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct ShadowDecisionQuery {
+    since: Option<u64>,
+    until: Option<u64>,
+    breach_prevented: Option<bool>,
+    direction: Option<DivergenceDirection>,
+    page_size: Option<u32>,
+    wait: Option<Duration>,
+}
+
+impl ShadowDecisionQuery {
+    /// Query matching every shadow decision
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Only decisions at or after `timestamp`
+    pub fn since(mut self, timestamp: u64) -> Self {
+        self.since = Some(timestamp);
+        self
+    }
+    
+    /// Only decisions before `timestamp`
+    pub fn until(mut self, timestamp: u64) -> Self {
+        self.until = Some(timestamp);
+        self
+    }
+    
+    /// Only decisions where a breach was (or was not) prevented
+    pub fn breach_prevented(mut self, breach_prevented: bool) -> Self {
+        self.breach_prevented = Some(breach_prevented);
+        self
+    }
+    
+    /// Only decisions where IAM and AF disagreed in `direction`
+    pub fn direction(mut self, direction: DivergenceDirection) -> Self {
+        self.direction = Some(direction);
+        self
+    }
+    
+    /// Decisions per page (server default if unset)
+    pub fn page_size(mut self, page_size: u32) -> Self {
+        self.page_size = Some(page_size);
+        self
+    }
+    
+    /// How long the server may hold a follow-mode poll open (default: 20s)
+    ///
+    /// Keep this below the client's request timeout.
+    pub fn wait(mut self, wait: Duration) -> Self {
+        self.wait = Some(wait);
+        self
+    }
+    
+    /// Long-poll wait for follow mode
+    pub(crate) fn wait_or_default(&self) -> Duration {
+        self.wait.unwrap_or(DEFAULT_FOLLOW_WAIT)
+    }
+    
+    /// Query string parameters, in a stable order
+    pub(crate) fn params(&self) -> Vec<(&'static str, String)> {
+        let mut params = Vec::new();
+        if let Some(since) = self.since {
+            params.push(("since", since.to_string()));
+        }
+        if let Some(until) = self.until {
+            params.push(("until", until.to_string()));
+        }
+        if let Some(breach_prevented) = self.breach_prevented {
+            params.push(("breach_prevented", breach_prevented.to_string()));
+        }
+        if let Some(direction) = self.direction {
+            params.push(("direction", direction.as_str().to_string()));
+        }
+        if let Some(page_size) = self.page_size {
+            params.push(("limit", page_size.to_string()));
+        }
+        params
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_empty_query_has_no_params() {
+        assert!(AuditLogQuery::new().params().is_empty());
+    }
+    
+    #[test]
+    fn test_params() {
+        let query = AuditLogQuery::new()
+            .until(200)
+            .since(100)
+            .result("denied")
+            .subject("alice")
+            .page_size(50);
+        
+        assert_eq!(
+            query.params(),
+            vec![
+                ("since", "100".to_string()),
+                ("until", "200".to_string()),
+                ("subject", "alice".to_string()),
+                ("result", "denied".to_string()),
+                ("limit", "50".to_string()),
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_shadow_decision_params() {
+        let query = ShadowDecisionQuery::new()
+            .direction(DivergenceDirection::IamDeniedAfAllowed)
+            .breach_prevented(false)
+            .since(100);
+        
+        assert_eq!(
+            query.params(),
+            vec![
+                ("since", "100".to_string()),
+                ("breach_prevented", "false".to_string()),
+                ("direction", "iam_denied_af_allowed".to_string()),
+            ]
+        );
+        assert_eq!(query.wait_or_default(), Duration::from_secs(20));
+    }
+}
@@ -0,0 +1,416 @@
+use crate::adapter::{IamAdapter, MalformedLine};
+use crate::idempotency::IdempotencyKey;
+use crate::types::ReplayResponse;
+use serde::{Deserialize, Serialize};
+
+/// Default number of log lines per uploaded chunk
+const DEFAULT_CHUNK_LINES: usize = 10_000;
+
+/// Default upper bound on the uncompressed size of a chunk
+const DEFAULT_MAX_CHUNK_BYTES: usize = 4 * 1024 * 1024;
+
+type ProgressCallback = Box<dyn FnMut(&ReplayProgress) + Send>;
+type MalformedCallback = Box<dyn FnMut(&MalformedLine) + Send>;
+
+/// Settings for a chunked IAM log replay
+///
+/// Used with [`Client::replay_iam_log_lines`](crate::Client::replay_iam_log_lines)
+/// and [`Client::replay_iam_log_reader`](crate::Client::replay_iam_log_reader).
+/// Each chunk is posted to the replay endpoint on its own; a chunk counts as
+/// acknowledged once the server has answered it.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::ReplayUpload;
+///
+/// let upload = ReplayUpload::new("aws-cloudtrail")
+///     .chunk_lines(5_000)
+///     .on_progress(|progress| {
+///         println!("{} lines replayed", progress.lines_acknowledged);
+///     });
+/// ```
+pub struct ReplayUpload {
+    pub(crate) adapter: String,
+    pub(crate) chunk_lines: usize,
+    pub(crate) max_chunk_bytes: usize,
+    pub(crate) gzip: bool,
+    pub(crate) idempotency_key: IdempotencyKey,
+    pub(crate) parser: Option<IamAdapter>,
+    pub(crate) progress: ReplayProgress,
+    pub(crate) on_progress: Option<ProgressCallback>,
+    pub(crate) on_malformed: Option<MalformedCallback>,
+}
+
+impl ReplayUpload {
+    /// Replay through `adapter`, e.g. `"aws-cloudtrail"`
+    pub fn new(adapter: impl Into<String>) -> Self {
+        Self {
+            adapter: adapter.into(),
+            chunk_lines: DEFAULT_CHUNK_LINES,
+            max_chunk_bytes: DEFAULT_MAX_CHUNK_BYTES,
+            gzip: false,
+            idempotency_key: IdempotencyKey::generate(),
+            parser: None,
+            progress: ReplayProgress::default(),
+            on_progress: None,
+            on_malformed: None,
+        }
+    }
+    
+    /// Replay through a typed adapter, validating each line locally
+    /// 
+    /// Lines its parser rejects are not uploaded; they are counted in
+    /// [`ReplayProgress::lines_rejected`] and passed to
+    /// [`on_malformed`](Self::on_malformed).
+    pub fn for_adapter(adapter: IamAdapter) -> Self {
+        Self {
+            parser: Some(adapter),
+            ..Self::new(adapter)
+        }
+    }
+    
+    /// Maximum input lines per chunk (default: 10,000)
+    ///
+    /// Blank and rejected lines count too, so this also bounds the rejected
+    /// lines reported per chunk. A line the adapter expands into several
+    /// records (e.g. a CloudTrail `Records` file) counts once.
+    pub fn chunk_lines(mut self, lines: usize) -> Self {
+        self.chunk_lines = lines.max(1);
+        self
+    }
+    
+    /// Maximum uncompressed bytes per chunk (default: 4 MiB)
+    ///
+    /// A single line larger than this is sent in a chunk of its own.
+    pub fn max_chunk_bytes(mut self, bytes: usize) -> Self {
+        self.max_chunk_bytes = bytes.max(1);
+        self
+    }
+    
+    /// Compress each chunk with gzip (`Content-Encoding: gzip`)
+    #[cfg(feature = "gzip")]
+    pub fn gzip(mut self, enabled: bool) -> Self {
+        self.gzip = enabled;
+        self
+    }
+    
+    /// Base of the per-chunk idempotency keys (default: random per upload)
+    ///
+    /// Each chunk is sent with this key suffixed by the chunking settings and
+    /// the position of its first line. Set the same key when resuming so that
+    /// chunks the server processed but never acknowledged are not counted
+    /// twice; changing [`chunk_lines`](Self::chunk_lines) or
+    /// [`max_chunk_bytes`](Self::max_chunk_bytes) on resume gives new keys.
+    pub fn idempotency_key(mut self, key: IdempotencyKey) -> Self {
+        self.idempotency_key = key;
+        self
+    }
+    
+    /// Called after every acknowledged chunk with the progress so far
+    ///
+    /// Persist the progress here to resume an interrupted replay with
+    /// [`resume_from`](Self::resume_from).
+    pub fn on_progress(mut self, callback: impl FnMut(&ReplayProgress) + Send + 'static) -> Self {
+        self.on_progress = Some(Box::new(callback));
+        self
+    }
+    
+    /// Called for every line rejected by the adapter's parser
+    pub fn on_malformed(mut self, callback: impl FnMut(&MalformedLine) + Send + 'static) -> Self {
+        self.on_malformed = Some(Box::new(callback));
+        self
+    }
+    
+    /// Continue an interrupted replay
+    ///
+    /// The input must be the same logs from the start: the lines already
+    /// acknowledged are read and skipped, and the counters carry on from
+    /// `progress`.
+    pub fn resume_from(mut self, progress: ReplayProgress) -> Self {
+        self.progress = progress;
+        self
+    }
+    
+    /// Key for the next chunk
+    ///
+    /// A chunk's content depends on where it starts and on the chunking
+    /// settings, so the key covers all three.
+    pub(crate) fn chunk_key(&self) -> IdempotencyKey {
+        self.idempotency_key.part(format_args!(
+            "{}-{}-{}",
+            self.chunk_lines, self.max_chunk_bytes, self.progress.lines_acknowledged
+        ))
+    }
+}
+
+impl std::fmt::Debug for ReplayUpload {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ReplayUpload")
+            .field("adapter", &self.adapter)
+            .field("chunk_lines", &self.chunk_lines)
+            .field("max_chunk_bytes", &self.max_chunk_bytes)
+            .field("gzip", &self.gzip)
+            .field("idempotency_key", &self.idempotency_key)
+            .field("parser", &self.parser)
+            .field("progress", &self.progress)
+            .finish_non_exhaustive()
+    }
+}
+
+/// Progress of a chunked replay, and its result once complete
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct ReplayProgress {
+    /// Chunks the server has answered
+    pub chunks_acknowledged: u64,
+    /// Input lines covered by those chunks, including skipped blank lines
+    pub lines_acknowledged: u64,
+    /// Lines rejected by local validation (see [`ReplayUpload::for_adapter`])
+    #[serde(default)]
+    pub lines_rejected: u64,
+    /// Sum of the per-chunk replay counters
+    pub totals: ReplayResponse,
+}
+
+impl ReplayProgress {
+    /// Count a chunk of `lines` input lines the server answered with `response`
+    pub(crate) fn acknowledge(&mut self, lines: u64, response: &ReplayResponse) {
+        self.chunks_acknowledged += 1;
+        self.lines_acknowledged += lines;
+        self.add_totals(response);
+    }
+    
+    /// Count input lines that needed no upload: blank or rejected ones
+    pub(crate) fn skip(&mut self, lines: u64) {
+        self.lines_acknowledged += lines;
+    }
+    
+    fn add_totals(&mut self, response: &ReplayResponse) {
+        self.totals.events_processed += response.events_processed;
+        self.totals.breaches_prevented += response.breaches_prevented;
+        self.totals.false_positives_avoided += response.false_positives_avoided;
+    }
+}
+
+/// Log lines ready to upload
+#[derive(Debug)]
+pub(crate) struct Chunk {
+    pub(crate) logs: Vec<String>,
+    /// Input lines consumed, including blank and rejected ones
+    pub(crate) input_lines: u64,
+    /// Lines rejected by local validation
+    pub(crate) rejected: Vec<MalformedLine>,
+}
+
+/// Groups input lines into bounded chunks, skipping already acknowledged ones
+pub(crate) struct Chunker {
+    max_lines: usize,
+    max_bytes: usize,
+    parser: Option<IamAdapter>,
+    skip: u64,
+    line_number: u64,
+    logs: Vec<String>,
+    bytes: usize,
+    input_lines: u64,
+    rejected: Vec<MalformedLine>,
+}
+
+impl Chunker {
+    pub(crate) fn new(upload: &ReplayUpload) -> Self {
+        Self {
+            max_lines: upload.chunk_lines,
+            max_bytes: upload.max_chunk_bytes,
+            parser: upload.parser,
+            skip: upload.progress.lines_acknowledged,
+            line_number: 0,
+            logs: Vec::new(),
+            bytes: 0,
+            input_lines: 0,
+            rejected: Vec::new(),
+        }
+    }
+    
+    /// Add a line; returns the previous chunk when this line does not fit
+    pub(crate) fn push(&mut self, line: String) -> Option<Chunk> {
+        self.line_number += 1;
+        if self.skip > 0 {
+            self.skip -= 1;
+            return None;
+        }
+        
+        let line = line.trim_end_matches(['\r', '\n']);
+        let records = if line.trim().is_empty() {
+            Vec::new()
+        } else if let Some(parser) = self.parser {
+            match parser.parse_line(line) {
+                Ok(records) => records,
+                Err(reason) => {
+                    let ready = self.make_room(0);
+                    self.input_lines += 1;
+                    self.rejected.push(MalformedLine { line: self.line_number, reason });
+                    return ready;
+                }
+            }
+        } else {
+            vec![line.to_string()]
+        };
+        
+        let size: usize = records.iter().map(String::len).sum();
+        let ready = self.make_room(size);
+        
+        self.input_lines += 1;
+        self.bytes += size;
+        self.logs.extend(records);
+        ready
+    }
+    
+    /// The final, partial chunk
+    pub(crate) fn finish(&mut self) -> Option<Chunk> {
+        if self.logs.is_empty() && self.rejected.is_empty() {
+            None
+        } else {
+            self.take()
+        }
+    }
+    
+    /// Take the current chunk if a line of `size` bytes does not fit in it
+    ///
+    /// An oversized line still gets a chunk of its own.
+    fn make_room(&mut self, size: usize) -> Option<Chunk> {
+        let lines_full = self.input_lines >= self.max_lines as u64;
+        let bytes_full = !self.logs.is_empty() && self.bytes + size > self.max_bytes;
+        if lines_full || bytes_full {
+            self.take()
+        } else {
+            None
+        }
+    }
+    
+    fn take(&mut self) -> Option<Chunk> {
+        let chunk = Chunk {
+            logs: std::mem::take(&mut self.logs),
+            input_lines: self.input_lines,
+            rejected: std::mem::take(&mut self.rejected),
+        };
+        self.bytes = 0;
+        self.input_lines = 0;
+        Some(chunk)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn chunks(upload: ReplayUpload, lines: &[&str]) -> Vec<(Vec<String>, u64)> {
+        let mut chunker = Chunker::new(&upload);
+        let mut chunks: Vec<Chunk> = lines
+            .iter()
+            .filter_map(|line| chunker.push(line.to_string()))
+            .collect();
+        chunks.extend(chunker.finish());
+        chunks.into_iter().map(|c| (c.logs, c.input_lines)).collect()
+    }
+    
+    #[test]
+    fn test_chunks_by_line_count() {
+        let chunks = chunks(ReplayUpload::new("okta").chunk_lines(2), &["a", "b", "c"]);
+        
+        assert_eq!(chunks, vec![(vec!["a".into(), "b".into()], 2), (vec!["c".into()], 1)]);
+    }
+    
+    #[test]
+    fn test_rejected_lines_bounded_per_chunk() {
+        let mut chunker = Chunker::new(&ReplayUpload::for_adapter(IamAdapter::Okta).chunk_lines(2));
+        
+        let mut chunks: Vec<Chunk> = (0..5).filter_map(|_| chunker.push("oops".to_string())).collect();
+        chunks.extend(chunker.finish());
+        
+        let rejected: Vec<usize> = chunks.iter().map(|chunk| chunk.rejected.len()).collect();
+        assert_eq!(rejected, vec![2, 2, 1]);
+        assert!(chunks.iter().all(|chunk| chunk.logs.is_empty()));
+        assert_eq!(chunks[2].rejected[0].line, 5);
+    }
+    
+    #[test]
+    fn test_expanded_line_counts_once() {
+        let line = r#"{"Records": [
+            {"eventTime": "t", "eventName": "a", "eventSource": "s", "userIdentity": {}},
+            {"eventTime": "t", "eventName": "b", "eventSource": "s", "userIdentity": {}}
+        ]}"#.replace('\n', "");
+        let mut chunker = Chunker::new(&ReplayUpload::for_adapter(IamAdapter::AwsCloudTrail).chunk_lines(1));
+        
+        assert!(chunker.push(line.clone()).is_none());
+        let chunk = chunker.push(line).unwrap();
+        assert_eq!(chunk.logs.len(), 2);
+        assert_eq!(chunk.input_lines, 1);
+    }
+    
+    #[test]
+    fn test_chunks_by_size() {
+        let chunks = chunks(ReplayUpload::new("okta").max_chunk_bytes(4), &["aa", "bb", "ccccc", "d"]);
+        
+        assert_eq!(
+            chunks,
+            vec![
+                (vec!["aa".into(), "bb".into()], 2),
+                (vec!["ccccc".into()], 1),
+                (vec!["d".into()], 1),
+            ]
+        );
+    }
+    
+    #[test]
+    fn test_blank_lines_counted_not_sent() {
+        let chunks = chunks(ReplayUpload::new("okta"), &["a\n", "", "b\r\n"]);
+        
+        assert_eq!(chunks, vec![(vec!["a".into(), "b".into()], 3)]);
+    }
+    
+    #[test]
+    fn test_resume_skips_acknowledged_lines() {
+        let progress = ReplayProgress {
+            chunks_acknowledged: 1,
+            lines_acknowledged: 2,
+            lines_rejected: 0,
+            totals: ReplayResponse::default(),
+        };
+        let chunks = chunks(ReplayUpload::new("okta").resume_from(progress), &["a", "b", "c"]);
+        
+        assert_eq!(chunks, vec![(vec!["c".into()], 1)]);
+    }
+    
+    #[test]
+    fn test_chunk_key_covers_chunking() {
+        let key = IdempotencyKey::new("k").unwrap();
+        let upload = ReplayUpload::new("okta").idempotency_key(key.clone()).chunk_lines(2).max_chunk_bytes(64);
+        let progress = ReplayProgress {
+            lines_acknowledged: 4,
+            ..ReplayProgress::default()
+        };
+        
+        assert_eq!(upload.chunk_key().as_str(), "k-2-64-0");
+        let resumed = upload.resume_from(progress.clone());
+        assert_eq!(resumed.chunk_key().as_str(), "k-2-64-4");
+        let rechunked = ReplayUpload::new("okta").idempotency_key(key).chunk_lines(3).max_chunk_bytes(64);
+        assert_ne!(rechunked.resume_from(progress).chunk_key(), resumed.chunk_key());
+    }
+    
+    #[test]
+    fn test_adapter_rejects_malformed_lines() {
+        let upload = ReplayUpload::for_adapter(IamAdapter::Okta).resume_from(ReplayProgress {
+            lines_acknowledged: 1,
+            ..ReplayProgress::default()
+        });
+        let mut chunker = Chunker::new(&upload);
+        
+        assert!(chunker.push("skipped".to_string()).is_none());
+        assert!(chunker.push(r#"{"actor": {"id": "u1"}, "eventType": "e", "published": "p", "uuid": "1"}"#.to_string()).is_none());
+        assert!(chunker.push("oops".to_string()).is_none());
+        
+        let chunk = chunker.finish().unwrap();
+        assert_eq!(chunk.logs, vec![r#"{"actor":{"id":"u1"},"eventType":"e","published":"p","uuid":"1"}"#]);
+        assert_eq!(chunk.input_lines, 2);
+        assert_eq!(chunk.rejected.len(), 1);
+        assert_eq!(chunk.rejected[0].line, 3);
+    }
+}
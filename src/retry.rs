@@ -1,7 +1,12 @@
 use crate::error::{Result, TruthlinkedError};
-use std::time::Duration;
+use std::fmt;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
 
+/// Default upper bound on a single retry delay for policies without their own
+const DEFAULT_MAX_DELAY: Duration = Duration::from_secs(30);
+
 /// Retry configuration with exponential backoff
 #[derive(Debug, Clone)]
 pub struct RetryConfig {
@@ -64,76 +69,129 @@ impl RetryConfig {
     }
 }
 
-/// Retry executor with exponential backoff and jitter
-pub struct RetryExecutor {
-    config: RetryConfig,
+/// State of a request that just failed, passed to a [`RetryPolicy`]
+#[derive(Debug, Clone)]
+pub struct RetryContext {
+    /// Attempts made so far, including the one that failed (starts at 1)
+    pub attempt: u32,
+    /// Time since the first attempt started
+    pub elapsed: Duration,
+    /// Delay slept before the failed attempt, if it was a retry
+    pub last_delay: Option<Duration>,
+    honor_retry_after: bool,
 }
 
-impl RetryExecutor {
-    pub fn new(config: RetryConfig) -> Self {
-        Self { config }
+impl RetryContext {
+    /// Context for the `attempt`th failure, e.g. to test a custom policy
+    pub fn new(attempt: u32, elapsed: Duration, last_delay: Option<Duration>) -> Self {
+        Self {
+            attempt,
+            elapsed,
+            last_delay,
+            honor_retry_after: false,
+        }
     }
     
-    /// Execute operation with retries
-    pub async fn execute<F, Fut, T>(&self, mut operation: F) -> Result<T>
-    where
-        F: FnMut() -> Fut,
-        Fut: std::future::Future<Output = Result<T>>,
-    {
-        let mut last_error = None;
-        
-        for attempt in 0..self.config.max_attempts {
-            match operation().await {
-                Ok(result) => return Ok(result),
-                Err(e) => {
-                    // Don't retry certain errors
-                    if !self.should_retry(&e) {
-                        return Err(e);
-                    }
-                    
-                    last_error = Some(e);
-                    
-                    // Don't sleep after the last attempt
-                    if attempt + 1 < self.config.max_attempts {
-                        let delay = self.calculate_delay(attempt);
-                        sleep(delay).await;
-                    }
-                }
-            }
-        }
-        
-        Err(last_error.unwrap_or(TruthlinkedError::Network("Max retries exceeded".to_string())))
+    /// Treat rate limits as retryable, after the server-advised delay
+    pub fn honor_retry_after(mut self, enable: bool) -> Self {
+        self.honor_retry_after = enable;
+        self
     }
     
-    /// Determine if error should be retried
-    fn should_retry(&self, error: &TruthlinkedError) -> bool {
+    /// Whether `error` is worth retrying
+    /// 
+    /// Network and server errors are; rate limits only when the client
+    /// honors `Retry-After`.
+    pub fn is_retryable(&self, error: &TruthlinkedError) -> bool {
         match error {
             // Retry network errors
             TruthlinkedError::Network(_) => true,
             // Retry server errors
             TruthlinkedError::ServerError => true,
+            TruthlinkedError::ServiceUnavailable(_) => true,
             // Don't retry auth errors
             TruthlinkedError::Unauthorized => false,
             TruthlinkedError::Forbidden => false,
             // Don't retry client errors
             TruthlinkedError::InvalidRequest(_) => false,
-            // Don't retry rate limits (handle separately)
-            TruthlinkedError::RateLimitExceeded(_) => false,
+            // Retry rate limits only when waiting as advised
+            TruthlinkedError::RateLimitExceeded(_) => self.honor_retry_after,
             // Don't retry other errors
             _ => false,
         }
     }
     
+    /// The server-advised delay for `error`, if the client honors it
+    pub fn advised_delay(&self, error: &TruthlinkedError) -> Option<Duration> {
+        if self.honor_retry_after {
+            error.rate_limit().and_then(|info| info.advised_delay())
+        } else {
+            None
+        }
+    }
+}
+
+/// Decides whether a failed request is retried, and after how long
+/// 
+/// Built-in policies: [`RetryConfig`] (exponential backoff with jitter),
+/// [`DecorrelatedJitter`], [`FixedDelay`], [`NoRetry`], and [`Deadline`] to
+/// bound any of them by total elapsed time.
+/// 
+/// # Example
+/// ```rust
+/// use std::time::Duration;
+/// use truthlinked_sdk::{RetryContext, RetryPolicy, TruthlinkedError};
+/// 
+/// /// Retry network errors only, once, after a second
+/// #[derive(Debug)]
+/// struct NetworkOnce;
+/// 
+/// impl RetryPolicy for NetworkOnce {
+///     fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+///         let retry = context.attempt == 1 && matches!(error, TruthlinkedError::Network(_));
+///         retry.then_some(Duration::from_secs(1))
+///     }
+/// }
+/// ```
+pub trait RetryPolicy: fmt::Debug + Send + Sync {
+    /// Delay before retrying after `error`, or `None` to give up
+    fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration>;
+    
+    /// Give up once retrying would take longer than `total` overall
+    fn with_deadline(self, total: Duration) -> Deadline<Self>
+    where
+        Self: Sized,
+    {
+        Deadline { inner: self, total }
+    }
+}
+
+/// Exponential backoff with jitter
+impl RetryPolicy for RetryConfig {
+    fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+        if context.attempt >= self.max_attempts || !context.is_retryable(error) {
+            return None;
+        }
+        
+        let delay = match context.advised_delay(error) {
+            Some(advised) => advised.min(self.max_delay),
+            None => self.calculate_delay(context.attempt - 1),
+        };
+        Some(delay)
+    }
+}
+
+impl RetryConfig {
     /// Calculate delay with exponential backoff and jitter
     fn calculate_delay(&self, attempt: u32) -> Duration {
-        let base_delay = self.config.initial_delay.as_millis() as f64;
-        let exponential_delay = base_delay * self.config.backoff_multiplier.powi(attempt as i32);
-        let capped_delay = exponential_delay.min(self.config.max_delay.as_millis() as f64);
+        let base_delay = self.initial_delay.as_millis() as f64;
+        let exponential_delay = base_delay * self.backoff_multiplier.powi(attempt as i32);
+        let capped_delay = exponential_delay.min(self.max_delay.as_millis() as f64);
         
         // Add jitter to prevent thundering herd
-        let jitter = if self.config.jitter_factor > 0.0 {
+        let jitter = if self.jitter_factor > 0.0 {
             use rand::Rng;
-            let jitter_amount = capped_delay * self.config.jitter_factor;
+            let jitter_amount = capped_delay * self.jitter_factor;
             
             rand::thread_rng().gen_range(-jitter_amount..=jitter_amount)
         } else {
@@ -145,6 +203,194 @@ impl RetryExecutor {
     }
 }
 
+/// "Decorrelated jitter" backoff: each delay is random between `base` and
+/// three times the previous one, capped at `max`
+#[derive(Debug, Clone)]
+pub struct DecorrelatedJitter {
+    base: Duration,
+    max: Duration,
+    max_attempts: u32,
+}
+
+impl DecorrelatedJitter {
+    /// Delays from `base` up to `max`, 3 attempts by default
+    pub fn new(base: Duration, max: Duration) -> Self {
+        Self { base, max, max_attempts: 3 }
+    }
+    
+    /// Maximum number of attempts, including the first
+    pub fn max_attempts(mut self, max_attempts: u32) -> Self {
+        self.max_attempts = max_attempts;
+        self
+    }
+}
+
+impl RetryPolicy for DecorrelatedJitter {
+    fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+        if context.attempt >= self.max_attempts || !context.is_retryable(error) {
+            return None;
+        }
+        if let Some(advised) = context.advised_delay(error) {
+            return Some(advised.min(self.max));
+        }
+        
+        use rand::Rng;
+        let upper = context.last_delay.unwrap_or(self.base).saturating_mul(3).max(self.base);
+        let delay = rand::thread_rng().gen_range(self.base..=upper);
+        Some(delay.min(self.max))
+    }
+}
+
+/// The same delay before every retry
+/// 
+/// Server-advised delays replace it when honored, up to
+/// [`max_delay`](Self::max_delay).
+#[derive(Debug, Clone)]
+pub struct FixedDelay {
+    delay: Duration,
+    max_delay: Duration,
+    max_attempts: u32,
+}
+
+impl FixedDelay {
+    /// Wait `delay` between attempts, making at most `max_attempts`
+    pub fn new(delay: Duration, max_attempts: u32) -> Self {
+        Self {
+            delay,
+            max_delay: DEFAULT_MAX_DELAY.max(delay),
+            max_attempts,
+        }
+    }
+    
+    /// Longest server-advised delay to wait (default: 30s, or `delay` if longer)
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+}
+
+impl RetryPolicy for FixedDelay {
+    fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+        if context.attempt >= self.max_attempts || !context.is_retryable(error) {
+            return None;
+        }
+        match context.advised_delay(error) {
+            Some(advised) => Some(advised.min(self.max_delay)),
+            None => Some(self.delay),
+        }
+    }
+}
+
+/// Never retry
+#[derive(Debug, Clone, Copy, Default)]
+pub struct NoRetry;
+
+impl RetryPolicy for NoRetry {
+    fn next_delay(&self, _error: &TruthlinkedError, _context: &RetryContext) -> Option<Duration> {
+        None
+    }
+}
+
+/// Bounds another policy by total elapsed time
+/// 
+/// A retry is only made if its delay ends within `total` of the first attempt.
+/// Built with [`RetryPolicy::with_deadline`].
+#[derive(Debug, Clone)]
+pub struct Deadline<P> {
+    inner: P,
+    total: Duration,
+}
+
+impl<P: RetryPolicy> RetryPolicy for Deadline<P> {
+    fn next_delay(&self, error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+        let delay = self.inner.next_delay(error, context)?;
+        (context.elapsed + delay <= self.total).then_some(delay)
+    }
+}
+
+/// Runs an operation, retrying failures as a [`RetryPolicy`] decides
+#[derive(Debug, Clone)]
+/// 
+/// Whatever the policy returns, no single delay exceeds the executor's
+/// [`max_delay`](Self::max_delay).
+pub struct RetryExecutor {
+    policy: Arc<dyn RetryPolicy>,
+    honor_retry_after: bool,
+    max_delay: Duration,
+}
+
+impl RetryExecutor {
+    /// Executor for `config`, capping delays at its `max_delay`
+    pub fn new(config: RetryConfig) -> Self {
+        let max_delay = config.max_delay;
+        Self::with_policy(config).max_delay(max_delay)
+    }
+    
+    /// Executor driven by any retry policy, capping delays at 30s
+    pub fn with_policy(policy: impl RetryPolicy + 'static) -> Self {
+        Self::from_shared(Arc::new(policy))
+    }
+    
+    pub(crate) fn from_shared(policy: Arc<dyn RetryPolicy>) -> Self {
+        Self {
+            policy,
+            honor_retry_after: false,
+            max_delay: DEFAULT_MAX_DELAY,
+        }
+    }
+    
+    /// Retry rate-limited requests, waiting as long as the server advises
+    /// 
+    /// When enabled, 429 responses are retried and any `Retry-After` (or
+    /// exhausted-window reset) interval replaces the computed backoff,
+    /// capped by the policy and by [`max_delay`](Self::max_delay).
+    pub fn honor_retry_after(mut self, enable: bool) -> Self {
+        self.honor_retry_after = enable;
+        self
+    }
+    
+    /// Upper bound on any single retry delay
+    pub fn max_delay(mut self, max_delay: Duration) -> Self {
+        self.max_delay = max_delay;
+        self
+    }
+    
+    /// Whether rate-limited requests are retried here
+    pub(crate) fn retries_rate_limits(&self) -> bool {
+        self.honor_retry_after
+    }
+    
+    /// Execute operation with retries
+    pub async fn execute<F, Fut, T>(&self, mut operation: F) -> Result<T>
+    where
+        F: FnMut() -> Fut,
+        Fut: std::future::Future<Output = Result<T>>,
+    {
+        let started = Instant::now();
+        let mut last_delay = None;
+        
+        for attempt in 1.. {
+            let error = match operation().await {
+                Ok(result) => return Ok(result),
+                Err(e) => e,
+            };
+            
+            let context = RetryContext::new(attempt, started.elapsed(), last_delay)
+                .honor_retry_after(self.honor_retry_after);
+            match self.policy.next_delay(&error, &context) {
+                Some(delay) => {
+                    let delay = delay.min(self.max_delay);
+                    sleep(delay).await;
+                    last_delay = Some(delay);
+                }
+                None => return Err(error),
+            }
+        }
+        
+        Err(TruthlinkedError::Network("Max retries exceeded".to_string()))
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -198,4 +444,88 @@ mod tests {
         assert!(result.is_err());
         assert_eq!(attempt_count.load(Ordering::SeqCst), 1);
     }
+    
+    #[test]
+    fn test_retry_after_capped_by_max_delay() {
+        let info = crate::error::RateLimitInfo {
+            retry_after: Some(Duration::from_secs(2)),
+            ..Default::default()
+        };
+        let context = RetryContext::new(1, Duration::ZERO, None).honor_retry_after(true);
+        let policy = RetryConfig::production();
+        
+        let error = TruthlinkedError::RateLimitExceeded(info.clone());
+        assert_eq!(policy.next_delay(&error, &context), Some(Duration::from_secs(2)));
+        
+        let error = TruthlinkedError::ServiceUnavailable(crate::error::RateLimitInfo {
+            retry_after: Some(Duration::from_secs(3600)),
+            ..info
+        });
+        assert_eq!(policy.next_delay(&error, &context), Some(Duration::from_secs(10)));
+        
+        let context = RetryContext::new(1, Duration::ZERO, None);
+        assert_eq!(policy.next_delay(&TruthlinkedError::RateLimitExceeded("x".into()), &context), None);
+    }
+    
+    #[test]
+    fn test_builtin_policies() {
+        let error = TruthlinkedError::ServerError;
+        let first = RetryContext::new(1, Duration::ZERO, None);
+        
+        let fixed = FixedDelay::new(Duration::from_millis(50), 2);
+        assert_eq!(fixed.next_delay(&error, &first), Some(Duration::from_millis(50)));
+        assert_eq!(fixed.next_delay(&error, &RetryContext::new(2, Duration::ZERO, None)), None);
+        assert_eq!(fixed.next_delay(&TruthlinkedError::Forbidden, &first), None);
+        
+        let jitter = DecorrelatedJitter::new(Duration::from_millis(100), Duration::from_secs(1)).max_attempts(5);
+        for _ in 0..20 {
+            let context = RetryContext::new(2, Duration::ZERO, Some(Duration::from_millis(200)));
+            let delay = jitter.next_delay(&error, &context).unwrap();
+            assert!(delay >= Duration::from_millis(100) && delay <= Duration::from_millis(600));
+        }
+        
+        let bounded = fixed.clone().with_deadline(Duration::from_millis(120));
+        assert!(bounded.next_delay(&error, &RetryContext::new(1, Duration::from_millis(60), None)).is_some());
+        assert!(bounded.next_delay(&error, &RetryContext::new(1, Duration::from_millis(80), None)).is_none());
+        assert_eq!(NoRetry.next_delay(&error, &first), None);
+    }
+    
+    #[test]
+    fn test_fixed_delay_caps_retry_after() {
+        let error = TruthlinkedError::RateLimitExceeded(crate::error::RateLimitInfo {
+            retry_after: Some(Duration::from_secs(86_400)),
+            ..Default::default()
+        });
+        let context = RetryContext::new(1, Duration::ZERO, None).honor_retry_after(true);
+        
+        let fixed = FixedDelay::new(Duration::from_millis(50), 3);
+        assert_eq!(fixed.next_delay(&error, &context), Some(Duration::from_secs(30)));
+        let fixed = fixed.max_delay(Duration::from_secs(5));
+        assert_eq!(fixed.next_delay(&error, &context), Some(Duration::from_secs(5)));
+    }
+    
+    #[tokio::test]
+    async fn test_executor_caps_policy_delay() {
+        #[derive(Debug)]
+        struct OneDay;
+        
+        impl RetryPolicy for OneDay {
+            fn next_delay(&self, _error: &TruthlinkedError, context: &RetryContext) -> Option<Duration> {
+                (context.attempt == 1).then_some(Duration::from_secs(86_400))
+            }
+        }
+        
+        let executor = RetryExecutor::with_policy(OneDay).max_delay(Duration::from_millis(10));
+        let started = Instant::now();
+        let attempts = Arc::new(AtomicU32::new(0));
+        
+        let result: Result<()> = executor.execute(|| {
+            attempts.fetch_add(1, Ordering::SeqCst);
+            async { Err(TruthlinkedError::ServerError) }
+        }).await;
+        
+        assert!(result.is_err());
+        assert_eq!(attempts.load(Ordering::SeqCst), 2);
+        assert!(started.elapsed() < Duration::from_secs(1));
+    }
 }
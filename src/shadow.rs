@@ -0,0 +1,351 @@
+use crate::client::Client;
+use crate::error::Result;
+use crate::idempotency::IdempotencyKey;
+use crate::signing::RequestSigner;
+use crate::types::ShadowDecision;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::VecDeque;
+use std::sync::atomic::{AtomicU64, Ordering};
+use std::sync::{Arc, Mutex, RwLock};
+
+/// Default bound on divergences buffered between flushes
+const DEFAULT_CAPACITY: usize = 10_000;
+
+/// Whether a matching rule grants or refuses access
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum PolicyEffect {
+    #[default]
+    Allow,
+    Deny,
+}
+
+/// One AF policy rule
+///
+/// Each pattern is `*` (anything), a prefix ending in `*` (e.g. `"arn:aws:s3:::logs/*"`)
+/// or an exact value.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct PolicyRule {
+    pub principal: String,
+    pub action: String,
+    pub resource: String,
+    #[serde(default)]
+    pub effect: PolicyEffect,
+}
+
+impl PolicyRule {
+    /// Rule allowing `principal` to perform `action` on `resource`
+    pub fn allow(principal: impl Into<String>, action: impl Into<String>, resource: impl Into<String>) -> Self {
+        Self {
+            principal: principal.into(),
+            action: action.into(),
+            resource: resource.into(),
+            effect: PolicyEffect::Allow,
+        }
+    }
+    
+    /// Rule denying `principal` to perform `action` on `resource`
+    pub fn deny(principal: impl Into<String>, action: impl Into<String>, resource: impl Into<String>) -> Self {
+        Self {
+            effect: PolicyEffect::Deny,
+            ..Self::allow(principal, action, resource)
+        }
+    }
+    
+    fn matches(&self, decision: &IamDecision) -> bool {
+        pattern_matches(&self.principal, &decision.principal)
+            && pattern_matches(&self.action, &decision.action)
+            && pattern_matches(&self.resource, &decision.resource)
+    }
+}
+
+fn pattern_matches(pattern: &str, value: &str) -> bool {
+    match pattern.strip_suffix('*') {
+        Some(prefix) => value.starts_with(prefix),
+        None => pattern == value,
+    }
+}
+
+/// AF policy evaluated locally by a [`ShadowEvaluator`]
+///
+/// Default deny: a request is allowed if some rule allows it and no rule
+/// denies it. Fetch the tenant's policy with
+/// [`Client::get_shadow_policy`](crate::Client::get_shadow_policy), or build
+/// one from rules.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ShadowPolicy {
+    /// Server-assigned policy version, if fetched
+    #[serde(default)]
+    pub version: Option<String>,
+    pub rules: Vec<PolicyRule>,
+}
+
+impl ShadowPolicy {
+    /// Policy made of `rules`
+    pub fn new(rules: Vec<PolicyRule>) -> Self {
+        Self { version: None, rules }
+    }
+    
+    /// Whether AF would allow `decision`
+    pub fn allows(&self, decision: &IamDecision) -> bool {
+        let mut allowed = false;
+        for rule in self.rules.iter().filter(|rule| rule.matches(decision)) {
+            match rule.effect {
+                PolicyEffect::Deny => return false,
+                PolicyEffect::Allow => allowed = true,
+            }
+        }
+        allowed
+    }
+}
+
+/// An access decision made by the existing IAM system
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct IamDecision {
+    pub principal: String,
+    pub action: String,
+    pub resource: String,
+    pub iam_allowed: bool,
+}
+
+impl IamDecision {
+    /// `principal` performing `action` on `resource`, and whether IAM allowed it
+    pub fn new(
+        principal: impl Into<String>,
+        action: impl Into<String>,
+        resource: impl Into<String>,
+        iam_allowed: bool,
+    ) -> Self {
+        Self {
+            principal: principal.into(),
+            action: action.into(),
+            resource: resource.into(),
+            iam_allowed,
+        }
+    }
+}
+
+/// In-process shadow mode: compares IAM decisions with a cached AF policy
+///
+/// [`evaluate`](Self::evaluate) makes no network calls, so it can run on hot
+/// paths. Divergences are buffered (up to [`capacity`](Self::capacity); later
+/// ones are dropped and counted) until [`flush`](Self::flush) uploads them.
+/// A batch whose upload failed is kept aside and resent unchanged, under the
+/// same idempotency key, so the server stores it once.
+///
+/// # Example
+/// ```rust,no_run
+/// use truthlinked_sdk::{Client, IamDecision, ShadowEvaluator};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("https://api.truthlinked.org", "key")?;
+/// let evaluator = ShadowEvaluator::new(client.get_shadow_policy().await?);
+///
+/// // On every request
+/// let decision = evaluator.evaluate(&IamDecision::new("alice", "s3:GetObject", "arn:aws:s3:::logs/1", true));
+///
+/// // Periodically
+/// evaluator.flush(&client).await?;
+/// # Ok(())
+/// # }
+/// ```
+pub struct ShadowEvaluator {
+    policy: RwLock<Arc<ShadowPolicy>>,
+    pending: Mutex<VecDeque<ShadowDecision>>,
+    /// Batch whose upload failed, with the key it was sent under
+    unacknowledged: Mutex<Option<(IdempotencyKey, Vec<ShadowDecision>)>>,
+    capacity: usize,
+    dropped: AtomicU64,
+}
+
+impl ShadowEvaluator {
+    /// Evaluate against `policy`
+    pub fn new(policy: ShadowPolicy) -> Self {
+        Self {
+            policy: RwLock::new(Arc::new(policy)),
+            pending: Mutex::new(VecDeque::new()),
+            unacknowledged: Mutex::new(None),
+            capacity: DEFAULT_CAPACITY,
+            dropped: AtomicU64::new(0),
+        }
+    }
+    
+    /// Maximum buffered divergences (default: 10,000)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity;
+        self
+    }
+    
+    /// Replace the cached policy, e.g. after refetching it
+    pub fn set_policy(&self, policy: ShadowPolicy) {
+        *self.policy.write().unwrap_or_else(|e| e.into_inner()) = Arc::new(policy);
+    }
+    
+    /// The cached policy
+    pub fn policy(&self) -> Arc<ShadowPolicy> {
+        self.policy.read().unwrap_or_else(|e| e.into_inner()).clone()
+    }
+    
+    /// Compare `decision` with the AF policy, buffering it if they disagree
+    pub fn evaluate(&self, decision: &IamDecision) -> ShadowDecision {
+        let af_would_allow = self.policy().allows(decision);
+        
+        let mut id = [0u8; 16];
+        rand::thread_rng().fill_bytes(&mut id);
+        let shadow = ShadowDecision {
+            divergence_id: hex::encode(id),
+            iam_allowed: decision.iam_allowed,
+            af_would_allow,
+            breach_prevented: decision.iam_allowed && !af_would_allow,
+            timestamp: Some(RequestSigner::current_timestamp()),
+            principal: Some(decision.principal.clone()),
+            action: Some(decision.action.clone()),
+            resource: Some(decision.resource.clone()),
+        };
+        
+        if shadow.divergence().is_some() {
+            let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+            if pending.len() < self.capacity {
+                pending.push_back(shadow.clone());
+            } else {
+                self.dropped.fetch_add(1, Ordering::Relaxed);
+            }
+        }
+        shadow
+    }
+    
+    /// Buffered divergences not yet flushed, including a failed batch
+    pub fn pending(&self) -> usize {
+        let unacknowledged = self
+            .unacknowledged
+            .lock()
+            .unwrap_or_else(|e| e.into_inner())
+            .as_ref()
+            .map_or(0, |(_, batch)| batch.len());
+        unacknowledged + self.pending.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+    
+    /// Divergences discarded because the buffer was full
+    pub fn dropped(&self) -> u64 {
+        self.dropped.load(Ordering::Relaxed)
+    }
+    
+    /// Take the buffered divergences, e.g. to upload them another way
+    pub fn drain(&self) -> Vec<ShadowDecision> {
+        let mut decisions = self.take_unacknowledged().map(|(_, batch)| batch).unwrap_or_default();
+        decisions.extend(self.pending.lock().unwrap_or_else(|e| e.into_inner()).drain(..));
+        decisions
+    }
+    
+    /// Upload buffered divergences, returning how many were sent
+    ///
+    /// On failure the batch is kept, and the next flush resends it with the
+    /// same idempotency key before uploading newer divergences.
+    pub async fn flush(&self, client: &Client) -> Result<usize> {
+        let (key, batch) = match self.take_unacknowledged() {
+            Some(unacknowledged) => unacknowledged,
+            None => (
+                IdempotencyKey::generate(),
+                self.pending.lock().unwrap_or_else(|e| e.into_inner()).drain(..).collect(),
+            ),
+        };
+        if batch.is_empty() {
+            return Ok(0);
+        }
+        
+        match client.submit_shadow_decisions_with_key(&batch, key.clone()).await {
+            Ok(()) => Ok(batch.len()),
+            Err(e) => {
+                let mut unacknowledged = self.unacknowledged.lock().unwrap_or_else(|e| e.into_inner());
+                if unacknowledged.is_none() {
+                    *unacknowledged = Some((key, batch));
+                } else {
+                    // A concurrent flush failed too; its batch keeps the slot
+                    self.requeue(batch);
+                }
+                Err(e)
+            }
+        }
+    }
+    
+    fn take_unacknowledged(&self) -> Option<(IdempotencyKey, Vec<ShadowDecision>)> {
+        self.unacknowledged.lock().unwrap_or_else(|e| e.into_inner()).take()
+    }
+    
+    /// Put `batch` back at the front of the buffer, as far as capacity allows
+    fn requeue(&self, batch: Vec<ShadowDecision>) {
+        let mut pending = self.pending.lock().unwrap_or_else(|e| e.into_inner());
+        let room = self.capacity.saturating_sub(pending.len());
+        let requeued = batch.len().min(room);
+        self.dropped.fetch_add((batch.len() - requeued) as u64, Ordering::Relaxed);
+        for decision in batch.into_iter().take(requeued).rev() {
+            pending.push_front(decision);
+        }
+    }
+}
+
+impl std::fmt::Debug for ShadowEvaluator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("ShadowEvaluator")
+            .field("rules", &self.policy().rules.len())
+            .field("pending", &self.pending())
+            .field("dropped", &self.dropped())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    fn policy() -> ShadowPolicy {
+        ShadowPolicy::new(vec![
+            PolicyRule::allow("alice", "s3:Get*", "arn:aws:s3:::logs/*"),
+            PolicyRule::allow("*", "s3:GetObject", "arn:aws:s3:::public/*"),
+            PolicyRule::deny("*", "*", "arn:aws:s3:::logs/secret"),
+        ])
+    }
+    
+    #[test]
+    fn test_policy_matching() {
+        let policy = policy();
+        
+        assert!(policy.allows(&IamDecision::new("alice", "s3:GetObject", "arn:aws:s3:::logs/1", true)));
+        assert!(policy.allows(&IamDecision::new("bob", "s3:GetObject", "arn:aws:s3:::public/x", true)));
+        assert!(!policy.allows(&IamDecision::new("bob", "s3:GetObject", "arn:aws:s3:::logs/1", true)));
+        assert!(!policy.allows(&IamDecision::new("alice", "s3:PutObject", "arn:aws:s3:::logs/1", true)));
+        // Deny wins over allow
+        assert!(!policy.allows(&IamDecision::new("alice", "s3:GetObject", "arn:aws:s3:::logs/secret", true)));
+    }
+    
+    #[test]
+    fn test_only_divergences_buffered() {
+        let evaluator = ShadowEvaluator::new(policy());
+        
+        let agreed = evaluator.evaluate(&IamDecision::new("alice", "s3:GetObject", "arn:aws:s3:::logs/1", true));
+        assert!(agreed.af_would_allow && !agreed.breach_prevented);
+        assert_eq!(evaluator.pending(), 0);
+        
+        let breach = evaluator.evaluate(&IamDecision::new("bob", "s3:DeleteObject", "arn:aws:s3:::logs/1", true));
+        assert!(breach.breach_prevented);
+        evaluator.evaluate(&IamDecision::new("alice", "s3:GetObject", "arn:aws:s3:::logs/2", false));
+        
+        let pending = evaluator.drain();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].divergence_id, breach.divergence_id);
+        assert!(!pending[1].breach_prevented);
+    }
+    
+    #[test]
+    fn test_capacity_drops_excess() {
+        let evaluator = ShadowEvaluator::new(ShadowPolicy::default()).capacity(1);
+        
+        for _ in 0..3 {
+            evaluator.evaluate(&IamDecision::new("bob", "read", "doc", true));
+        }
+        assert_eq!(evaluator.pending(), 1);
+        assert_eq!(evaluator.dropped(), 2);
+    }
+}
@@ -0,0 +1,272 @@
+use crate::error::{Result, TruthlinkedError};
+use base64::{engine::general_purpose, Engine as _};
+use rustls::client::{ServerCertVerified, ServerCertVerifier, WebPkiVerifier};
+use rustls::{Certificate, CertificateError, ClientConfig, OwnedTrustAnchor, RootCertStore, ServerName};
+use rustls_pemfile::Item;
+use sha2::{Digest, Sha256};
+use std::error::Error as StdError;
+use std::fmt;
+use std::sync::Arc;
+use std::time::SystemTime;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Marker error returned by [`PinnedVerifier`] when no pin matches
+#[derive(Debug)]
+pub(crate) struct PinMismatch;
+
+impl fmt::Display for PinMismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("certificate pin mismatch")
+    }
+}
+
+impl StdError for PinMismatch {}
+
+/// Standard chain validation followed by an SPKI pin check
+///
+/// The connection is accepted if any certificate the server sent (leaf or
+/// intermediate) has a pinned key, so a backup pin on the issuing CA keeps
+/// working across leaf key rotations.
+struct PinnedVerifier {
+    inner: WebPkiVerifier,
+    pins: Vec<[u8; 32]>,
+}
+
+impl ServerCertVerifier for PinnedVerifier {
+    fn verify_server_cert(
+        &self,
+        end_entity: &Certificate,
+        intermediates: &[Certificate],
+        server_name: &ServerName,
+        scts: &mut dyn Iterator<Item = &[u8]>,
+        ocsp_response: &[u8],
+        now: SystemTime,
+    ) -> std::result::Result<ServerCertVerified, rustls::Error> {
+        let verified = self
+            .inner
+            .verify_server_cert(end_entity, intermediates, server_name, scts, ocsp_response, now)?;
+        
+        let pinned = std::iter::once(end_entity)
+            .chain(intermediates)
+            .filter_map(|cert| spki_sha256(&cert.0))
+            .any(|hash| self.pins.contains(&hash));
+        
+        if pinned {
+            Ok(verified)
+        } else {
+            tracing::error!("Server certificate chain matches no configured pin");
+            Err(rustls::Error::InvalidCertificate(CertificateError::Other(Arc::new(PinMismatch))))
+        }
+    }
+}
+
+/// TLS options collected by `ClientBuilder`
+#[derive(Debug, Clone)]
+pub(crate) struct TlsSettings {
+    pub(crate) pins: Vec<String>,
+    pub(crate) root_certificates: Vec<Certificate>,
+    pub(crate) built_in_roots: bool,
+    pub(crate) identity: Option<ClientIdentity>,
+}
+
+impl Default for TlsSettings {
+    fn default() -> Self {
+        Self {
+            pins: Vec::new(),
+            root_certificates: Vec::new(),
+            built_in_roots: true,
+            identity: None,
+        }
+    }
+}
+
+impl TlsSettings {
+    /// Whether reqwest's own TLS setup covers these settings
+    pub(crate) fn is_default(&self) -> bool {
+        self.pins.is_empty() && self.root_certificates.is_empty() && self.built_in_roots && self.identity.is_none()
+    }
+    
+    /// rustls configuration for these settings
+    pub(crate) fn client_config(&self) -> Result<ClientConfig> {
+        let mut roots = RootCertStore::empty();
+        if self.built_in_roots {
+            roots.add_trust_anchors(webpki_roots::TLS_SERVER_ROOTS.iter().map(|anchor| {
+                OwnedTrustAnchor::from_subject_spki_name_constraints(anchor.subject, anchor.spki, anchor.name_constraints)
+            }));
+        }
+        for cert in &self.root_certificates {
+            roots
+                .add(cert)
+                .map_err(|e| TruthlinkedError::InvalidRequest(format!("Invalid root certificate: {}", e)))?;
+        }
+        if roots.is_empty() {
+            return Err(TruthlinkedError::InvalidRequest("No trusted root certificates configured".to_string()));
+        }
+        
+        let inner = WebPkiVerifier::new(roots, None);
+        let verifier: Arc<dyn ServerCertVerifier> = if self.pins.is_empty() {
+            Arc::new(inner)
+        } else {
+            if self.pins.len() == 1 {
+                tracing::warn!("Only one certificate pin configured; add a backup pin to survive key rotation");
+            }
+            Arc::new(PinnedVerifier {
+                inner,
+                pins: self.pins.iter().map(|pin| parse_pin(pin)).collect::<Result<_>>()?,
+            })
+        };
+        
+        let builder = ClientConfig::builder()
+            .with_safe_defaults()
+            .with_custom_certificate_verifier(verifier);
+        match &self.identity {
+            // rustls keeps its own copy of the key for the client's lifetime
+            Some(identity) => builder
+                .with_client_auth_cert(identity.certs.clone(), rustls::PrivateKey(identity.key.0.clone()))
+                .map_err(|e| TruthlinkedError::InvalidRequest(format!("Invalid client identity: {}", e))),
+            None => Ok(builder.with_no_client_auth()),
+        }
+    }
+}
+
+/// Certificates from a PEM bundle
+pub(crate) fn parse_certificates(pem: &[u8]) -> Result<Vec<Certificate>> {
+    let certs = rustls_pemfile::certs(&mut &*pem)
+        .map_err(|_| TruthlinkedError::InvalidRequest("Invalid certificate PEM".to_string()))?;
+    if certs.is_empty() {
+        return Err(TruthlinkedError::InvalidRequest("No certificate found in PEM".to_string()));
+    }
+    Ok(certs.into_iter().map(Certificate).collect())
+}
+
+/// Client certificate chain and private key for mutual TLS
+#[derive(Clone)]
+pub(crate) struct ClientIdentity {
+    certs: Vec<Certificate>,
+    key: PrivateKeyDer,
+}
+
+/// DER-encoded private key, zeroized on drop
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+struct PrivateKeyDer(Vec<u8>);
+
+impl ClientIdentity {
+    /// Parse a PEM certificate chain and a PKCS#8, PKCS#1 or SEC1 PEM key
+    pub(crate) fn from_pem(cert_pem: &[u8], key_pem: &[u8]) -> Result<Self> {
+        let certs = parse_certificates(cert_pem)?;
+        let key = rustls_pemfile::read_all(&mut &*key_pem)
+            .ok()
+            .into_iter()
+            .flatten()
+            .find_map(|item| match item {
+                Item::PKCS8Key(key) | Item::RSAKey(key) | Item::ECKey(key) => Some(PrivateKeyDer(key)),
+                _ => None,
+            })
+            .ok_or_else(|| TruthlinkedError::InvalidRequest("No private key found in PEM".to_string()))?;
+        
+        Ok(Self { certs, key })
+    }
+}
+
+impl fmt::Debug for ClientIdentity {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("ClientIdentity")
+            .field("certs", &self.certs.len())
+            .field("key", &"[REDACTED]")
+            .finish()
+    }
+}
+
+/// Decode a base64 SPKI SHA-256 pin, with or without a `sha256/` prefix
+fn parse_pin(pin: &str) -> Result<[u8; 32]> {
+    let encoded = pin.strip_prefix("sha256/").unwrap_or(pin);
+    general_purpose::STANDARD
+        .decode(encoded)
+        .ok()
+        .and_then(|hash| <[u8; 32]>::try_from(hash).ok())
+        .ok_or_else(|| TruthlinkedError::InvalidRequest(format!("Invalid certificate pin: {}", pin)))
+}
+
+/// SHA-256 of the DER `SubjectPublicKeyInfo` of an X.509 certificate
+pub(crate) fn spki_sha256(cert_der: &[u8]) -> Option<[u8; 32]> {
+    // Certificate ::= SEQUENCE { tbsCertificate, signatureAlgorithm, signature }
+    let (_, certificate, _) = der_element(cert_der)?;
+    let (_, mut tbs, _) = der_element(certificate)?;
+    
+    // Optional [0] version, then serialNumber, signature, issuer, validity, subject
+    if tbs.first() == Some(&0xa0) {
+        tbs = der_element(tbs)?.2;
+    }
+    for _ in 0..5 {
+        tbs = der_element(tbs)?.2;
+    }
+    
+    let (spki, _, _) = der_element(tbs)?;
+    Some(Sha256::digest(spki).into())
+}
+
+/// Split one DER element off `input`: (whole element, contents, rest)
+fn der_element(input: &[u8]) -> Option<(&[u8], &[u8], &[u8])> {
+    let first = *input.get(1)?;
+    let (header, length) = if first < 0x80 {
+        (2, first as usize)
+    } else {
+        let count = (first & 0x7f) as usize;
+        if count == 0 || count > 4 {
+            return None;
+        }
+        let length = input.get(2..2 + count)?.iter().fold(0usize, |acc, byte| (acc << 8) | *byte as usize);
+        (2 + count, length)
+    };
+    
+    let end = header.checked_add(length)?;
+    let element = input.get(..end)?;
+    Some((element, &element[header..], &input[end..]))
+}
+
+/// Whether `err` was caused by [`PinnedVerifier`] rejecting the server
+pub(crate) fn is_pin_mismatch(err: &(dyn StdError + 'static)) -> bool {
+    let mut source = Some(err);
+    while let Some(err) = source {
+        if let Some(rustls::Error::InvalidCertificate(CertificateError::Other(other))) = err.downcast_ref() {
+            return other.is::<PinMismatch>();
+        }
+        // rustls errors reach reqwest wrapped in (possibly nested) io::Errors,
+        // whose source() skips the wrapped error itself
+        source = match err.downcast_ref::<std::io::Error>().and_then(|io| io.get_ref()) {
+            Some(inner) => Some(inner as &(dyn StdError + 'static)),
+            None => err.source(),
+        };
+    }
+    false
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_spki_hash_matches_public_key() {
+        let cert = rcgen::generate_simple_self_signed(vec!["localhost".to_string()]).unwrap();
+        let pin = general_purpose::STANDARD.encode(Sha256::digest(cert.get_key_pair().public_key_der()));
+        
+        let hash = spki_sha256(&cert.serialize_der().unwrap()).unwrap();
+        assert_eq!(general_purpose::STANDARD.encode(hash), pin);
+        assert_eq!(parse_pin(&format!("sha256/{}", pin)).unwrap(), hash);
+        assert!(parse_pin("c2hvcnQ=").is_err());
+        assert!(spki_sha256(b"\x30\x05\x30").is_none());
+    }
+    
+    #[test]
+    fn test_identity_debug_redacts_key() {
+        let cert = rcgen::generate_simple_self_signed(vec!["client".to_string()]).unwrap();
+        let identity = ClientIdentity::from_pem(
+            cert.serialize_pem().unwrap().as_bytes(),
+            cert.serialize_private_key_pem().as_bytes(),
+        )
+        .unwrap();
+        
+        assert_eq!(format!("{:?}", identity), "ClientIdentity { certs: 1, key: \"[REDACTED]\" }");
+        assert!(ClientIdentity::from_pem(cert.serialize_pem().unwrap().as_bytes(), b"").is_err());
+    }
+}
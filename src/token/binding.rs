@@ -0,0 +1,89 @@
+use crate::error::{Result, TruthlinkedError};
+use base64::Engine;
+use sha2::{Digest, Sha256};
+
+/// Exporter label for `tls-exporter` channel bindings (RFC 9266)
+pub const TLS_EXPORTER_LABEL: &str = "EXPORTER-Channel-Binding";
+
+/// The 32-byte channel binding sent with `exchange_token`
+///
+/// A channel binding ties an AF token to the TLS connection it was issued
+/// over, so a token lifted from one connection is useless on another.
+/// Derive it from the connection rather than generating random bytes:
+///
+/// - [`from_tls_exporter`](Self::from_tls_exporter): the `tls-exporter`
+///   binding (RFC 9266), 32 bytes of keying material exported with the
+///   label [`TLS_EXPORTER_LABEL`] and no context. Unique per connection;
+///   preferred on TLS 1.3.
+/// - [`from_certificate_der`](Self::from_certificate_der): the
+///   `tls-server-end-point` binding (RFC 5929), the SHA-256 hash of the
+///   server's end-entity certificate. Stable across connections to the same
+///   server, and available where keying material cannot be exported.
+///
+/// RFC 5929 hashes with the certificate's own signature digest, upgrading
+/// MD5 and SHA-1 to SHA-256. Since the binding is 32 bytes, SHA-256 is
+/// always used here; this matches RFC 5929 for the usual SHA-256 (and
+/// weaker) signed certificates.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::ChannelBinding;
+///
+/// # let server_cert_der: Vec<u8> = vec![];
+/// let binding = ChannelBinding::from_certificate_der(&server_cert_der);
+/// let channel_binding: [u8; 32] = binding.into();
+/// ```
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ChannelBinding([u8; 32]);
+
+impl ChannelBinding {
+    /// Use exported TLS keying material as the binding
+    ///
+    /// `keying_material` must be the 32 bytes exported with
+    /// [`TLS_EXPORTER_LABEL`], e.g. rustls'
+    /// `export_keying_material(&mut out, TLS_EXPORTER_LABEL.as_bytes(), None)`.
+    pub fn from_tls_exporter(keying_material: &[u8]) -> Result<Self> {
+        let bytes: [u8; 32] = keying_material.try_into().map_err(|_| {
+            TruthlinkedError::InvalidRequest("TLS exporter value must be 32 bytes".to_string())
+        })?;
+        Ok(Self(bytes))
+    }
+    
+    /// Derive the `tls-server-end-point` binding from a DER certificate
+    pub fn from_certificate_der(certificate_der: &[u8]) -> Self {
+        Self(Sha256::digest(certificate_der).into())
+    }
+    
+    /// Derive the `tls-server-end-point` binding from a PEM certificate
+    ///
+    /// Uses the first `CERTIFICATE` block, which is the end-entity
+    /// certificate in a server's chain.
+    pub fn from_certificate_pem(certificate_pem: &str) -> Result<Self> {
+        const BEGIN: &str = "-----BEGIN CERTIFICATE-----";
+        const END: &str = "-----END CERTIFICATE-----";
+        let invalid = || TruthlinkedError::InvalidRequest("Invalid PEM certificate".to_string());
+        
+        let start = certificate_pem.find(BEGIN).ok_or_else(invalid)? + BEGIN.len();
+        let end = certificate_pem[start..].find(END).ok_or_else(invalid)? + start;
+        let body: String = certificate_pem[start..end]
+            .chars()
+            .filter(|c| !c.is_ascii_whitespace())
+            .collect();
+        let der = base64::engine::general_purpose::STANDARD
+            .decode(body)
+            .map_err(|_| invalid())?;
+        
+        Ok(Self::from_certificate_der(&der))
+    }
+    
+    /// The raw binding bytes
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+}
+
+impl From<ChannelBinding> for [u8; 32] {
+    fn from(binding: ChannelBinding) -> Self {
+        binding.0
+    }
+}
@@ -0,0 +1,238 @@
+use super::nonce::NonceGenerator;
+use crate::client::Client;
+use crate::error::Result;
+use crate::signing::RequestSigner;
+use crate::types::TokenResponse;
+use std::collections::HashMap;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use zeroize::{Zeroize, ZeroizeOnDrop};
+
+/// Default time before `expires_at` at which a cached token is refreshed
+const DEFAULT_REFRESH_SKEW: Duration = Duration::from_secs(60);
+
+/// An AF token held by [`TokenManager`], zeroized when dropped
+///
+/// Tokens are shared between callers through `Arc`; the token string is
+/// wiped once the manager has evicted it and the last caller drops it.
+#[derive(Clone, Zeroize, ZeroizeOnDrop)]
+pub struct AfToken {
+    token: String,
+    granted_scope: Vec<String>,
+    expires_at: u64,
+    exchange_id: String,
+}
+
+impl AfToken {
+    /// The AF token to present as a bearer credential
+    pub fn as_str(&self) -> &str {
+        &self.token
+    }
+    
+    /// Scope granted by the server, possibly narrower than requested
+    pub fn granted_scope(&self) -> &[String] {
+        &self.granted_scope
+    }
+    
+    /// Expiry in Unix seconds
+    pub fn expires_at(&self) -> u64 {
+        self.expires_at
+    }
+    
+    /// Token id accepted by [`Client::validate_token`]
+    pub fn exchange_id(&self) -> &str {
+        &self.exchange_id
+    }
+    
+    fn expired(&self, now: u64) -> bool {
+        now >= self.expires_at
+    }
+}
+
+impl From<TokenResponse> for AfToken {
+    fn from(mut response: TokenResponse) -> Self {
+        Self {
+            token: std::mem::take(&mut response.af_token),
+            granted_scope: std::mem::take(&mut response.granted_scope),
+            expires_at: response.expires_at,
+            exchange_id: std::mem::take(&mut response.exchange_id),
+        }
+    }
+}
+
+impl std::fmt::Debug for AfToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("AfToken")
+            .field("token", &"[REDACTED]")
+            .field("granted_scope", &self.granted_scope)
+            .field("expires_at", &self.expires_at)
+            .field("exchange_id", &self.exchange_id)
+            .finish()
+    }
+}
+
+/// Cache key: SSO subject plus the sorted, de-duplicated requested scope
+type CacheKey = (String, Vec<String>);
+
+/// One cache entry; the async lock makes concurrent refreshes single-flight
+type Slot = Arc<tokio::sync::Mutex<Option<Arc<AfToken>>>>;
+
+/// Caches `exchange_token` results per SSO subject and requested scope
+///
+/// [`token`](Self::token) returns the cached [`AfToken`] while it is valid
+/// and exchanges a new one once the cached token is within
+/// [`refresh_skew`](Self::refresh_skew) of its `expires_at`. Concurrent
+/// callers for the same subject and scope share a single exchange.
+///
+/// If a proactive refresh fails while the cached token has not yet expired,
+/// the cached token is returned and the refresh is retried on the next call.
+///
+/// Replaced and evicted tokens are zeroized once no caller holds them.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use truthlinked_sdk::{ChannelBinding, Client, TokenManager};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(Client::new("https://api.truthlinked.org", "tl_pro_...")?);
+/// # let server_cert_der: Vec<u8> = vec![];
+/// let manager = TokenManager::new(client, ChannelBinding::from_certificate_der(&server_cert_der));
+///
+/// # let sso_token = "";
+/// let token = manager.token("alice", sso_token, &["read:users".to_string()]).await?;
+/// println!("Expires at {}", token.expires_at());
+/// # Ok(())
+/// # }
+/// ```
+pub struct TokenManager {
+    client: Arc<Client>,
+    slots: Mutex<HashMap<CacheKey, Slot>>,
+    refresh_skew: Duration,
+    channel_binding: [u8; 32],
+    nonces: NonceGenerator,
+}
+
+impl TokenManager {
+    /// Create a manager that exchanges tokens through `client`
+    ///
+    /// Every exchange sends `channel_binding`, typically a
+    /// [`ChannelBinding`](crate::ChannelBinding) derived from the connection
+    /// the tokens will be used on, so verifiers can check it. Nonces come
+    /// from a [`NonceGenerator`], so none is reused within its replay window.
+    pub fn new(client: Arc<Client>, channel_binding: impl Into<[u8; 32]>) -> Self {
+        Self {
+            client,
+            slots: Mutex::new(HashMap::new()),
+            refresh_skew: DEFAULT_REFRESH_SKEW,
+            channel_binding: channel_binding.into(),
+            nonces: NonceGenerator::new(),
+        }
+    }
+    
+    /// How long before `expires_at` to refresh a cached token (default: 60s)
+    pub fn refresh_skew(mut self, skew: Duration) -> Self {
+        self.refresh_skew = skew;
+        self
+    }
+    
+    /// Get a valid AF token for `subject` and `requested_scope`
+    ///
+    /// `sso_token` is only sent to the server when no usable token is cached.
+    /// The order of `requested_scope` does not matter.
+    pub async fn token(
+        &self,
+        subject: &str,
+        sso_token: &str,
+        requested_scope: &[String],
+    ) -> Result<Arc<AfToken>> {
+        let mut scope = requested_scope.to_vec();
+        scope.sort();
+        scope.dedup();
+        
+        let slot = self.slot((subject.to_string(), scope.clone()));
+        let mut cached = slot.lock().await;
+        
+        let now = RequestSigner::current_timestamp();
+        if let Some(token) = cached.as_ref() {
+            if now.saturating_add(self.refresh_skew.as_secs()) < token.expires_at {
+                return Ok(token.clone());
+            }
+        }
+        
+        let nonce = self.nonces.generate();
+        match self.client.exchange_token(sso_token, scope, nonce, self.channel_binding).await {
+            Ok(response) => {
+                let token = Arc::new(AfToken::from(response));
+                *cached = Some(token.clone());
+                Ok(token)
+            }
+            Err(e) => match cached.as_ref() {
+                Some(token) if !token.expired(now) => {
+                    tracing::warn!(error = %e, "AF token refresh failed, using cached token");
+                    Ok(token.clone())
+                }
+                _ => {
+                    *cached = None;
+                    Err(e)
+                }
+            },
+        }
+    }
+    
+    /// Drop the cached token for `subject` and `requested_scope`
+    pub fn invalidate(&self, subject: &str, requested_scope: &[String]) {
+        let mut scope = requested_scope.to_vec();
+        scope.sort();
+        scope.dedup();
+        
+        self.slots.lock().unwrap_or_else(|e| e.into_inner()).remove(&(subject.to_string(), scope));
+    }
+    
+    /// Drop every cached token
+    pub fn clear(&self) {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner()).clear();
+    }
+    
+    /// Number of cached subject and scope combinations
+    pub fn len(&self) -> usize {
+        self.slots.lock().unwrap_or_else(|e| e.into_inner()).len()
+    }
+    
+    /// Whether nothing is cached
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    
+    /// Get or create the slot for `key`, evicting expired entries on insert
+    fn slot(&self, key: CacheKey) -> Slot {
+        let mut slots = self.slots.lock().unwrap_or_else(|e| e.into_inner());
+        if let Some(slot) = slots.get(&key) {
+            return slot.clone();
+        }
+        
+        // Entries a caller is still using are left alone
+        let now = RequestSigner::current_timestamp();
+        slots.retain(|_, slot| {
+            if Arc::strong_count(slot) > 1 {
+                return true;
+            }
+            match slot.try_lock() {
+                Ok(cached) => cached.as_ref().is_some_and(|token| !token.expired(now)),
+                Err(_) => true,
+            }
+        });
+        
+        slots.entry(key).or_default().clone()
+    }
+}
+
+impl std::fmt::Debug for TokenManager {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenManager")
+            .field("cached", &self.len())
+            .field("refresh_skew", &self.refresh_skew)
+            .finish_non_exhaustive()
+    }
+}
@@ -0,0 +1,26 @@
+//! AF token handling
+//!
+//! AF tokens issued by [`Client::exchange_token`](crate::Client::exchange_token)
+//! are compact JWS strings: `HEADER.CLAIMS.SIGNATURE`, each part base64url
+//! without padding. The header names the algorithm (`EdDSA`) and the issuer
+//! key version (`kid`); the signature is Ed25519 over `HEADER.CLAIMS`.
+//!
+//! Claims carried by every AF token:
+//!
+//! | Claim | Meaning |
+//! |-------|---------|
+//! | `sub` | Subject the token was issued to |
+//! | `scope` | Granted scope, as in `TokenResponse::granted_scope` |
+//! | `exp` | Expiry in Unix seconds, as in `TokenResponse::expires_at` |
+//! | `jti` | Token id, as in `TokenResponse::exchange_id` |
+//! | `cb` | Hex channel binding supplied at exchange time |
+
+mod binding;
+mod manager;
+mod nonce;
+mod verifier;
+
+pub use binding::{ChannelBinding, TLS_EXPORTER_LABEL};
+pub use manager::{AfToken, TokenManager};
+pub use nonce::NonceGenerator;
+pub use verifier::{TokenRequirements, TokenVerifier};
@@ -0,0 +1,163 @@
+use rand::RngCore;
+use std::collections::{HashSet, VecDeque};
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+
+/// Default time a nonce is remembered for replay detection
+const DEFAULT_WINDOW: Duration = Duration::from_secs(300);
+
+/// Default bound on the number of remembered nonces
+const DEFAULT_CAPACITY: usize = 100_000;
+
+#[derive(Default)]
+struct ReplayWindow {
+    seen: HashSet<[u8; 32]>,
+    order: VecDeque<(Instant, [u8; 32])>,
+}
+
+impl ReplayWindow {
+    fn expire(&mut self, now: Instant, window: Duration, capacity: usize) {
+        while let Some((recorded, nonce)) = self.order.front() {
+            if now.duration_since(*recorded) < window && self.order.len() < capacity {
+                break;
+            }
+            self.seen.remove(nonce);
+            self.order.pop_front();
+        }
+    }
+    
+    fn record(&mut self, now: Instant, nonce: [u8; 32]) -> bool {
+        if !self.seen.insert(nonce) {
+            return false;
+        }
+        self.order.push_back((now, nonce));
+        true
+    }
+}
+
+/// Generates `exchange_token` nonces and detects replays
+///
+/// Nonces are 32 bytes from the operating system's CSPRNG. Every nonce
+/// generated or accepted is remembered for the replay window, so the same
+/// generator never hands out a value twice within it and
+/// [`check_and_record`](Self::check_and_record) rejects a nonce seen before.
+///
+/// Memory is bounded by [`capacity`](Self::capacity): once full, the oldest
+/// nonces are forgotten early, so size the capacity for your peak rate over
+/// the window.
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::NonceGenerator;
+///
+/// let nonces = NonceGenerator::new();
+/// let nonce = nonces.generate();
+///
+/// // A second sighting of the same nonce is a replay
+/// assert!(!nonces.check_and_record(&nonce));
+/// ```
+pub struct NonceGenerator {
+    window: Duration,
+    capacity: usize,
+    state: Mutex<ReplayWindow>,
+}
+
+impl NonceGenerator {
+    /// Create a generator with a 5 minute window and room for 100,000 nonces
+    pub fn new() -> Self {
+        Self {
+            window: DEFAULT_WINDOW,
+            capacity: DEFAULT_CAPACITY,
+            state: Mutex::new(ReplayWindow::default()),
+        }
+    }
+    
+    /// How long a nonce is remembered (default: 5 minutes)
+    ///
+    /// Should cover at least the server's accepted clock skew for exchanges.
+    pub fn window(mut self, window: Duration) -> Self {
+        self.window = window;
+        self
+    }
+    
+    /// Maximum number of nonces remembered at once (default: 100,000)
+    pub fn capacity(mut self, capacity: usize) -> Self {
+        self.capacity = capacity.max(1);
+        self
+    }
+    
+    /// Generate a fresh nonce and remember it
+    pub fn generate(&self) -> [u8; 32] {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.expire(now, self.window, self.capacity);
+        
+        loop {
+            let mut nonce = [0u8; 32];
+            rand::rngs::OsRng.fill_bytes(&mut nonce);
+            if state.record(now, nonce) {
+                return nonce;
+            }
+        }
+    }
+    
+    /// Record a nonce received from a peer
+    ///
+    /// Returns `false` if it was already seen within the window.
+    pub fn check_and_record(&self, nonce: &[u8; 32]) -> bool {
+        let mut state = self.state.lock().unwrap();
+        let now = Instant::now();
+        state.expire(now, self.window, self.capacity);
+        state.record(now, *nonce)
+    }
+    
+    /// Number of nonces currently remembered
+    pub fn len(&self) -> usize {
+        self.state.lock().unwrap().order.len()
+    }
+    
+    /// Whether no nonces are remembered
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl Default for NonceGenerator {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl std::fmt::Debug for NonceGenerator {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("NonceGenerator")
+            .field("window", &self.window)
+            .field("capacity", &self.capacity)
+            .field("remembered", &self.len())
+            .finish()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_window_expiry() {
+        let nonces = NonceGenerator::new().window(Duration::ZERO);
+        let nonce = nonces.generate();
+        
+        // Forgotten immediately with a zero window
+        assert!(nonces.check_and_record(&nonce));
+    }
+    
+    #[test]
+    fn test_capacity_bound() {
+        let nonces = NonceGenerator::new().capacity(3);
+        for _ in 0..10 {
+            nonces.generate();
+        }
+        
+        assert_eq!(nonces.len(), 3);
+    }
+}
@@ -0,0 +1,335 @@
+use crate::client::Client;
+use crate::error::Result;
+use crate::signing::RequestSigner;
+use crate::types::ValidateResponse;
+use crate::witness::keyring::decode_public_key;
+use base64::Engine;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use std::collections::HashMap;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use tokio::sync::RwLock;
+
+/// Default tolerance for clock skew when checking `exp`
+const DEFAULT_LEEWAY: Duration = Duration::from_secs(30);
+
+/// Default minimum time between issuer key fetches
+const DEFAULT_KEY_REFRESH_INTERVAL: Duration = Duration::from_secs(60);
+
+#[derive(Deserialize)]
+struct TokenHeader {
+    alg: String,
+    kid: u32,
+}
+
+#[derive(Deserialize)]
+struct TokenClaims {
+    sub: String,
+    #[serde(default)]
+    scope: Vec<String>,
+    exp: u64,
+    jti: String,
+    #[serde(default)]
+    cb: Option<String>,
+}
+
+/// A token split into its parts, signature not yet checked
+struct DecodedToken<'a> {
+    header: TokenHeader,
+    claims: TokenClaims,
+    signing_input: &'a [u8],
+    signature: Signature,
+}
+
+impl<'a> DecodedToken<'a> {
+    fn parse(token: &'a str) -> Option<Self> {
+        let token = token.trim();
+        let (signing_input, signature) = token.rsplit_once('.')?;
+        let (header, claims) = signing_input.split_once('.')?;
+        
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let header: TokenHeader = serde_json::from_slice(&engine.decode(header).ok()?).ok()?;
+        let claims: TokenClaims = serde_json::from_slice(&engine.decode(claims).ok()?).ok()?;
+        let signature = Signature::from_slice(&engine.decode(signature).ok()?).ok()?;
+        
+        Some(Self {
+            header,
+            claims,
+            signing_input: signing_input.as_bytes(),
+            signature,
+        })
+    }
+}
+
+/// What a token must grant to be accepted
+///
+/// # Example
+/// ```rust
+/// use truthlinked_sdk::TokenRequirements;
+///
+/// let requirements = TokenRequirements::new()
+///     .scope("read:users")
+///     .channel_binding([7u8; 32]);
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct TokenRequirements {
+    scope: Vec<String>,
+    channel_binding: Option<[u8; 32]>,
+}
+
+impl TokenRequirements {
+    /// Accept any unexpired token with a valid signature
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Require `scope` to be among the token's granted scope
+    pub fn scope(mut self, scope: impl Into<String>) -> Self {
+        self.scope.push(scope.into());
+        self
+    }
+    
+    /// Require the token to be bound to this channel
+    ///
+    /// Pass the [`ChannelBinding`](crate::ChannelBinding) of the connection
+    /// the token arrived on. Tokens without a `cb` claim are rejected.
+    pub fn channel_binding(mut self, channel_binding: impl Into<[u8; 32]>) -> Self {
+        self.channel_binding = Some(channel_binding.into());
+        self
+    }
+    
+    fn satisfied_by(&self, granted_scope: &[String], channel_binding: Option<&str>) -> bool {
+        let scope_granted = self
+            .scope
+            .iter()
+            .all(|required| granted_scope.contains(required));
+        
+        let binding_matches = match &self.channel_binding {
+            Some(expected) => channel_binding
+                .map(|actual| actual.eq_ignore_ascii_case(&hex::encode(expected)))
+                .unwrap_or(false),
+            None => true,
+        };
+        
+        scope_granted && binding_matches
+    }
+}
+
+/// Issuer keys and when they were last fetched
+#[derive(Default)]
+struct KeyCache {
+    keys: HashMap<u32, VerifyingKey>,
+    fetched_at: Option<Instant>,
+}
+
+/// Verifies AF tokens locally against cached issuer keys
+///
+/// [`Client::validate_token`] makes an HTTP call per check. `TokenVerifier`
+/// instead checks the token's Ed25519 signature, `exp`, scope and channel
+/// binding in-process, and returns the same [`ValidateResponse`] shape so
+/// callers can switch between the two.
+///
+/// The server is only consulted when:
+/// - the token is signed by a key version not in the cache: the issuer keys
+///   are refetched from [`Client::get_token_keys`] (at most once per
+///   [`key_refresh_interval`](Self::key_refresh_interval)); if the key is
+///   still unknown the token is rejected, or with
+///   [`server_fallback`](Self::server_fallback) validated by the server
+/// - revocation checking is enabled with
+///   [`check_revocation`](Self::check_revocation), in which case every token
+///   that passes local checks is also confirmed with the server
+///
+/// Tokens that are malformed, expired, badly signed or that do not meet the
+/// [`TokenRequirements`] yield `ValidateResponse { valid: false, .. }`.
+/// Errors are reserved for failures to reach the server.
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use truthlinked_sdk::{Client, TokenRequirements, TokenVerifier};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(Client::new("https://api.truthlinked.org", "key")?);
+/// let verifier = TokenVerifier::new(client);
+///
+/// # let af_token = "";
+/// let requirements = TokenRequirements::new().scope("read:users");
+/// let result = verifier.verify(af_token, &requirements).await?;
+/// if result.valid {
+///     println!("Authorized: {:?}", result.subject);
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct TokenVerifier {
+    client: Arc<Client>,
+    cache: RwLock<KeyCache>,
+    leeway: Duration,
+    key_refresh_interval: Duration,
+    check_revocation: bool,
+    server_fallback: bool,
+}
+
+impl TokenVerifier {
+    /// Create a verifier that fetches issuer keys through `client`
+    ///
+    /// Keys are fetched lazily on the first verification.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            cache: RwLock::new(KeyCache::default()),
+            leeway: DEFAULT_LEEWAY,
+            key_refresh_interval: DEFAULT_KEY_REFRESH_INTERVAL,
+            check_revocation: false,
+            server_fallback: false,
+        }
+    }
+    
+    /// Tolerance for clock skew when checking expiry (default: 30s)
+    pub fn leeway(mut self, leeway: Duration) -> Self {
+        self.leeway = leeway;
+        self
+    }
+    
+    /// Minimum time between issuer key fetches (default: 60s)
+    ///
+    /// Bounds how often tokens with unknown key versions can make the
+    /// verifier hit the network.
+    pub fn key_refresh_interval(mut self, interval: Duration) -> Self {
+        self.key_refresh_interval = interval;
+        self
+    }
+    
+    /// Confirm every locally valid token with [`Client::validate_token`]
+    /// (default: disabled)
+    ///
+    /// Enable this where a revoked token must be rejected before it expires.
+    pub fn check_revocation(mut self, enabled: bool) -> Self {
+        self.check_revocation = enabled;
+        self
+    }
+    
+    /// Validate tokens signed by an unknown key with the server
+    /// (default: disabled)
+    ///
+    /// The whole token is sent with [`Client::validate_bearer_token`], the
+    /// body-carrying counterpart of [`Client::validate_token`], so the server
+    /// checks its signature. Enable this where the issuer may sign with a key
+    /// it has not yet published.
+    pub fn server_fallback(mut self, enabled: bool) -> Self {
+        self.server_fallback = enabled;
+        self
+    }
+    
+    /// Fetch the issuer's current keys, replacing the cache
+    ///
+    /// Keys no longer published by the issuer are dropped.
+    pub async fn refresh_keys(&self) -> Result<()> {
+        let mut cache = self.cache.write().await;
+        self.fetch_keys(&mut cache).await
+    }
+    
+    /// Verify an AF token against `requirements`
+    pub async fn verify(&self, token: &str, requirements: &TokenRequirements) -> Result<ValidateResponse> {
+        let decoded = match DecodedToken::parse(token) {
+            Some(decoded) if decoded.header.alg == "EdDSA" => decoded,
+            _ => return Ok(invalid()),
+        };
+        let claims = &decoded.claims;
+        
+        let now = RequestSigner::current_timestamp();
+        if now > claims.exp.saturating_add(self.leeway.as_secs()) {
+            return Ok(invalid());
+        }
+        
+        // Without the signing key none of the claims can be trusted, not even `jti`
+        let confirmed_by_server = match self.issuer_key(decoded.header.kid).await? {
+            Some(key) => {
+                if key.verify(decoded.signing_input, &decoded.signature).is_err() {
+                    return Ok(invalid());
+                }
+                false
+            }
+            None if self.server_fallback => {
+                if !self.client.validate_bearer_token(token).await?.valid {
+                    return Ok(invalid());
+                }
+                true
+            }
+            None => {
+                tracing::warn!(kid = decoded.header.kid, "Rejecting token signed by an unknown issuer key");
+                return Ok(invalid());
+            }
+        };
+        
+        if !requirements.satisfied_by(&claims.scope, claims.cb.as_deref()) {
+            return Ok(invalid());
+        }
+        
+        if self.check_revocation
+            && !confirmed_by_server
+            && !self.client.validate_token(claims.jti.as_str()).await?.valid
+        {
+            return Ok(invalid());
+        }
+        
+        Ok(ValidateResponse {
+            valid: true,
+            subject: Some(claims.sub.clone()),
+            scope: Some(claims.scope.clone()),
+        })
+    }
+    
+    /// Look up a key version, refetching the issuer keys if it is unknown
+    async fn issuer_key(&self, key_version: u32) -> Result<Option<VerifyingKey>> {
+        if let Some(key) = self.cache.read().await.keys.get(&key_version) {
+            return Ok(Some(*key));
+        }
+        
+        let mut cache = self.cache.write().await;
+        // Another task may have refreshed while we waited for the lock
+        let refresh_due = cache
+            .fetched_at
+            .map(|at| at.elapsed() >= self.key_refresh_interval)
+            .unwrap_or(true);
+        if !cache.keys.contains_key(&key_version) && refresh_due {
+            self.fetch_keys(&mut cache).await?;
+        }
+        
+        Ok(cache.keys.get(&key_version).copied())
+    }
+    
+    async fn fetch_keys(&self, cache: &mut KeyCache) -> Result<()> {
+        let key_set = self.client.get_token_keys().await?;
+        
+        let mut keys = HashMap::with_capacity(key_set.keys.len());
+        for entry in key_set.keys {
+            keys.insert(entry.key_version, decode_public_key(&entry.public_key)?);
+        }
+        
+        cache.keys = keys;
+        cache.fetched_at = Some(Instant::now());
+        Ok(())
+    }
+}
+
+impl std::fmt::Debug for TokenVerifier {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.debug_struct("TokenVerifier")
+            .field("leeway", &self.leeway)
+            .field("key_refresh_interval", &self.key_refresh_interval)
+            .field("check_revocation", &self.check_revocation)
+            .field("server_fallback", &self.server_fallback)
+            .finish_non_exhaustive()
+    }
+}
+
+fn invalid() -> ValidateResponse {
+    ValidateResponse {
+        valid: false,
+        subject: None,
+        scope: None,
+    }
+}
@@ -1,3 +1,4 @@
+use crate::compliance::{ComplianceFinding, ReportPeriod};
 use serde::{Deserialize, Serialize};
 
 /// License tier
@@ -26,6 +27,12 @@ pub struct TokenRequest {
     pub channel_binding: String,
 }
 
+/// Bearer token validation request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ValidateTokenRequest {
+    pub token: String,
+}
+
 /// Token exchange response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct TokenResponse {
@@ -43,13 +50,127 @@ pub struct ValidateResponse {
     pub scope: Option<Vec<String>>,
 }
 
+/// Authorization check for one token and permission
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct AuthorizationRequest {
+    pub token: String,
+    pub permission: String,
+}
+
+/// Authorization decision
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct AuthorizationDecision {
+    pub authorized: bool,
+    /// Why the decision was reached, when the server explains it
+    #[serde(default)]
+    pub reason: Option<String>,
+    /// Identifier of the decision in the audit log
+    #[serde(default)]
+    pub decision_id: Option<String>,
+}
+
+/// Batch authorization request
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchAuthorizationRequest {
+    pub requests: Vec<AuthorizationRequest>,
+}
+
+/// Batch authorization response, one decision per request in order
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct BatchAuthorizationResponse {
+    pub decisions: Vec<AuthorizationDecision>,
+}
+
+/// Token issuer public key
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenSigningKey {
+    pub key_version: u32,
+    /// Ed25519 public key (base64 raw bytes or SPKI PEM)
+    pub public_key: String,
+}
+
+/// Token issuer public keys
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct TokenKeySet {
+    pub keys: Vec<TokenSigningKey>,
+}
+
 /// Shadow decision
+///
+/// Non-exhaustive so that the server can report more context; build one
+/// with [`ShadowDecision::new`].
 #[derive(Debug, Clone, Serialize, Deserialize)]
+#[non_exhaustive]
 pub struct ShadowDecision {
     pub divergence_id: String,
     pub iam_allowed: bool,
     pub af_would_allow: bool,
     pub breach_prevented: bool,
+    /// When the divergence was recorded (Unix seconds), if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<u64>,
+    /// Who made the request, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub principal: Option<String>,
+    /// What was attempted, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub action: Option<String>,
+    /// What it was attempted on, if reported
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub resource: Option<String>,
+}
+
+impl ShadowDecision {
+    /// Decision `divergence_id`, without timestamp or request context
+    ///
+    /// `breach_prevented` is set when IAM allowed what AF would deny.
+    pub fn new(divergence_id: impl Into<String>, iam_allowed: bool, af_would_allow: bool) -> Self {
+        Self {
+            divergence_id: divergence_id.into(),
+            iam_allowed,
+            af_would_allow,
+            breach_prevented: iam_allowed && !af_would_allow,
+            timestamp: None,
+            principal: None,
+            action: None,
+            resource: None,
+        }
+    }
+    
+    /// Which way IAM and AF disagreed, or `None` if they agreed
+    pub fn divergence(&self) -> Option<DivergenceDirection> {
+        match (self.iam_allowed, self.af_would_allow) {
+            (true, false) => Some(DivergenceDirection::IamAllowedAfDenied),
+            (false, true) => Some(DivergenceDirection::IamDeniedAfAllowed),
+            _ => None,
+        }
+    }
+}
+
+/// Direction of an IAM vs AF disagreement in shadow mode
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "snake_case")]
+pub enum DivergenceDirection {
+    /// IAM allowed a request AF would have denied (a prevented breach)
+    IamAllowedAfDenied,
+    /// IAM denied a request AF would have allowed
+    IamDeniedAfAllowed,
+}
+
+impl DivergenceDirection {
+    /// Wire name, as used in query strings
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Self::IamAllowedAfDenied => "iam_allowed_af_denied",
+            Self::IamDeniedAfAllowed => "iam_denied_af_allowed",
+        }
+    }
+}
+
+/// Locally evaluated shadow decisions uploaded in one call
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ShadowDecisionBatch {
+    pub decisions: Vec<ShadowDecision>,
 }
 
 /// Shadow replay request
@@ -60,7 +181,7 @@ pub struct ReplayRequest {
 }
 
 /// Shadow replay response
-#[derive(Debug, Clone, Serialize, Deserialize)]
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
 pub struct ReplayResponse {
     pub events_processed: u64,
     pub breaches_prevented: u64,
@@ -70,19 +191,30 @@ pub struct ReplayResponse {
 /// SOX compliance report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct SoxReport {
-    pub period: String,
+    /// Reporting period; see [`ReportPeriod`] for the accepted forms
+    pub period: ReportPeriod,
     pub total_events: u64,
     pub audit_trail_complete: bool,
     pub no_gaps: bool,
+    /// Intervals missing from the audit trail; empty when `no_gaps`
+    #[serde(default)]
+    pub gaps: Vec<ReportPeriod>,
+    /// Per-control results behind the summary flags
+    #[serde(default)]
+    pub findings: Vec<ComplianceFinding>,
 }
 
 /// PCI-DSS compliance report
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct PciReport {
-    pub period: String,
+    /// Reporting period; see [`ReportPeriod`] for the accepted forms
+    pub period: ReportPeriod,
     pub access_controls_enforced: bool,
     pub encryption_verified: bool,
     pub audit_complete: bool,
+    /// Per-control results behind the summary flags
+    #[serde(default)]
+    pub findings: Vec<ComplianceFinding>,
 }
 
 /// Audit log entry
@@ -148,6 +280,17 @@ pub struct SignedTreeHead {
     pub key_version: u32,
 }
 
+/// Consistency proof between two tree sizes
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ConsistencyProof {
+    #[serde(rename = "firstSize")]
+    pub first_size: u64,
+    #[serde(rename = "secondSize")]
+    pub second_size: u64,
+    /// Hex-encoded node hashes (RFC 9162 section 2.1.4)
+    pub proof: Vec<String>,
+}
+
 /// Witness health response
 #[derive(Debug, Clone, Serialize, Deserialize)]
 pub struct WitnessHealthResponse {
@@ -0,0 +1,263 @@
+use super::merkle::{decode_hash, leaf_hash, Hash, MerkleAccumulator};
+use crate::error::VerificationError;
+use crate::types::{SignedTreeHead, WitnessEvent};
+use sha2::{Digest, Sha256};
+
+/// `prev_hash` of the first event in the log (sequence 0): 32 zero bytes
+pub const GENESIS_PREV_HASH: &str =
+    "0000000000000000000000000000000000000000000000000000000000000000";
+
+/// Iterator over the events in an export bundle
+///
+/// Created by [`parse_export`]. Yields one item per non-blank line, in
+/// file order; a line that is not a valid event yields
+/// [`VerificationError::MalformedExport`] with its 1-based line number.
+pub struct ExportReader<'a> {
+    remaining: &'a [u8],
+    line: usize,
+}
+
+impl Iterator for ExportReader<'_> {
+    type Item = Result<WitnessEvent, VerificationError>;
+    
+    fn next(&mut self) -> Option<Self::Item> {
+        while !self.remaining.is_empty() {
+            let end = self
+                .remaining
+                .iter()
+                .position(|byte| *byte == b'\n')
+                .unwrap_or(self.remaining.len());
+            let line = &self.remaining[..end];
+            self.remaining = self.remaining.get(end + 1..).unwrap_or_default();
+            self.line += 1;
+            
+            if line.iter().all(u8::is_ascii_whitespace) {
+                continue;
+            }
+            return Some(
+                serde_json::from_slice(line)
+                    .map_err(|_| VerificationError::MalformedExport { line: self.line }),
+            );
+        }
+        None
+    }
+}
+
+/// Parse an export bundle from [`Client::export_witness_chain`](crate::Client::export_witness_chain)
+///
+/// # Format
+/// Exports are UTF-8 JSON Lines: one [`WitnessEvent`] object per line, in
+/// ascending `sequence` order, using the same field names as the API.
+/// Blank lines are ignored. Parsing is lazy, so arbitrarily large exports
+/// can be walked without materializing every event.
+pub fn parse_export(data: &[u8]) -> ExportReader<'_> {
+    ExportReader {
+        remaining: data,
+        line: 0,
+    }
+}
+
+/// Recompute a witness event's `event_hash`
+///
+/// The hash is SHA-256 over the UTF-8 text below, one field per line with
+/// integers in decimal and hashes as they appear in the event:
+///
+/// ```text
+/// PREV_HASH
+/// SEQUENCE
+/// TIMESTAMP
+/// AF_EVENT_HASH
+/// AF_MERKLE_ROOT
+/// AF_SEQUENCE
+/// AF_INSTANCE_ID
+/// ORACLE_TIME
+/// AF_SIGNATURE
+/// ```
+pub fn compute_event_hash(event: &WitnessEvent) -> Hash {
+    let submission = &event.submission;
+    let payload = format!(
+        "{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}\n{}",
+        event.prev_hash,
+        event.sequence,
+        event.timestamp,
+        submission.af_event_hash,
+        submission.af_merkle_root,
+        submission.af_sequence,
+        submission.af_instance_id,
+        submission.oracle_time,
+        submission.af_signature,
+    );
+    Sha256::digest(payload.as_bytes()).into()
+}
+
+/// Summary of a successfully verified export
+#[derive(Debug, Clone, PartialEq)]
+pub struct ExportSummary {
+    /// Number of events verified
+    pub events: u64,
+    /// Sequence number of the first event, if any
+    pub first_sequence: Option<u64>,
+    /// Sequence number of the last event, if any
+    pub last_sequence: Option<u64>,
+    /// `event_hash` of the last event, the chain head
+    pub head_hash: Option<String>,
+}
+
+/// Incremental verifier for a hash-chained sequence of witness events
+///
+/// Feed events in order with [`push`](Self::push); each call checks sequence
+/// continuity, the `prev_hash` link to the previous event and the event's
+/// own `event_hash`. [`finish`](Self::finish) additionally checks the Merkle
+/// root over the events against a signed tree head.
+///
+/// A verifier created with [`new`](Self::new) expects the chain to start at
+/// the genesis event. Use [`resume_after`](Self::resume_after) to verify a
+/// segment that continues from an event you have already verified.
+#[derive(Debug, Clone)]
+pub struct ChainVerifier {
+    next_sequence: u64,
+    prev_hash: String,
+    from_genesis: bool,
+    tree: MerkleAccumulator,
+    summary: ExportSummary,
+}
+
+impl Default for ChainVerifier {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl ChainVerifier {
+    /// Verify a chain starting at sequence 0
+    pub fn new() -> Self {
+        Self::starting_at(0, GENESIS_PREV_HASH.to_string(), true)
+    }
+    
+    /// Verify a segment that continues after a known event
+    ///
+    /// The segment's first event must have sequence `sequence + 1` and
+    /// `prev_hash` equal to `event_hash`. Segments cannot be checked against
+    /// a tree head's root, since the earlier leaves are not available.
+    /// Fails if `sequence` is `u64::MAX`, which nothing can follow.
+    pub fn resume_after(sequence: u64, event_hash: impl Into<String>) -> Result<Self, VerificationError> {
+        let next_sequence = sequence
+            .checked_add(1)
+            .ok_or(VerificationError::SequenceOverflow { sequence })?;
+        Ok(Self::starting_at(next_sequence, event_hash.into(), false))
+    }
+    
+    fn starting_at(next_sequence: u64, prev_hash: String, from_genesis: bool) -> Self {
+        Self {
+            next_sequence,
+            prev_hash,
+            from_genesis,
+            tree: MerkleAccumulator::new(),
+            summary: ExportSummary {
+                events: 0,
+                first_sequence: None,
+                last_sequence: None,
+                head_hash: None,
+            },
+        }
+    }
+    
+    /// Check the next event in the chain
+    pub fn push(&mut self, event: &WitnessEvent) -> Result<(), VerificationError> {
+        if event.sequence != self.next_sequence {
+            return Err(VerificationError::SequenceGap {
+                expected: self.next_sequence,
+                found: event.sequence,
+            });
+        }
+        
+        if !event.prev_hash.eq_ignore_ascii_case(&self.prev_hash) {
+            return Err(VerificationError::ChainBroken { sequence: event.sequence });
+        }
+        
+        let claimed = decode_hash(&event.event_hash).ok_or(VerificationError::InvalidHash("event_hash"))?;
+        if compute_event_hash(event) != claimed {
+            return Err(VerificationError::EventHashMismatch { sequence: event.sequence });
+        }
+        let next_sequence = event
+            .sequence
+            .checked_add(1)
+            .ok_or(VerificationError::SequenceOverflow { sequence: event.sequence })?;
+        
+        self.tree.push(leaf_hash(&claimed));
+        self.prev_hash = event.event_hash.clone();
+        self.next_sequence = next_sequence;
+        
+        self.summary.events += 1;
+        self.summary.first_sequence.get_or_insert(event.sequence);
+        self.summary.last_sequence = Some(event.sequence);
+        self.summary.head_hash = Some(event.event_hash.clone());
+        
+        Ok(())
+    }
+    
+    /// Summary of the events verified so far
+    pub fn summary(&self) -> &ExportSummary {
+        &self.summary
+    }
+    
+    /// Finish verification by checking the Merkle root against a tree head
+    ///
+    /// The chain must start at genesis and contain exactly `sth.tree_size`
+    /// events, so that the recomputed root covers the same leaves.
+    pub fn finish(self, sth: &SignedTreeHead) -> Result<ExportSummary, VerificationError> {
+        if !self.from_genesis {
+            return Err(VerificationError::PartialChain);
+        }
+        if self.tree.len() != sth.tree_size {
+            return Err(VerificationError::EventCountMismatch {
+                events: self.tree.len(),
+                tree_size: sth.tree_size,
+            });
+        }
+        
+        let expected = decode_hash(&sth.root_hash).ok_or(VerificationError::InvalidHash("root_hash"))?;
+        let computed = self.tree.root();
+        if computed != expected {
+            return Err(VerificationError::RootMismatch {
+                expected: sth.root_hash.clone(),
+                computed: hex::encode(computed),
+            });
+        }
+        
+        Ok(self.summary)
+    }
+}
+
+/// Verify a complete export bundle against a signed tree head, offline
+///
+/// Parses the bundle (see [`parse_export`]), walks the `prev_hash` →
+/// `event_hash` chain from genesis, checks sequence continuity, recomputes
+/// every event hash and finally compares the Merkle root over all events
+/// with `sth.root_hash`. The export must cover exactly the events in the
+/// tree head, i.e. be requested with `start_seq = 0` and
+/// `end_seq = sth.tree_size - 1`.
+///
+/// Verify the tree head's signature separately with a
+/// [`WitnessKeyring`](super::WitnessKeyring).
+///
+/// # Example
+/// ```rust,no_run
+/// use truthlinked_sdk::{witness, SignedTreeHead};
+///
+/// # fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let bundle = std::fs::read("witness-export.jsonl")?;
+/// let sth: SignedTreeHead = serde_json::from_slice(&std::fs::read("sth.json")?)?;
+///
+/// let summary = witness::verify_export(&bundle, &sth)?;
+/// println!("Verified {} events", summary.events);
+/// # Ok(())
+/// # }
+/// ```
+pub fn verify_export(data: &[u8], sth: &SignedTreeHead) -> Result<ExportSummary, VerificationError> {
+    let mut verifier = ChainVerifier::new();
+    for event in parse_export(data) {
+        verifier.push(&event?)?;
+    }
+    verifier.finish(sth)
+}
@@ -0,0 +1,185 @@
+use crate::error::{Result, TruthlinkedError, VerificationError};
+use crate::types::SignedTreeHead;
+use base64::Engine;
+use ed25519_dalek::pkcs8::DecodePublicKey;
+use ed25519_dalek::{Signature, Verifier, VerifyingKey};
+use serde::Deserialize;
+use std::collections::BTreeMap;
+use std::path::Path;
+
+/// Trusted witness signing keys, indexed by `key_version`
+///
+/// The witness service rotates its Ed25519 signing key over time and stamps
+/// each [`SignedTreeHead`] with the version of the key that signed it. A
+/// keyring holds every version you are prepared to trust.
+///
+/// # Key File Formats
+/// A PEM file holds a single `-----BEGIN PUBLIC KEY-----` block (SPKI) and
+/// is loaded under an explicit key version with [`WitnessKeyring::add_pem`].
+///
+/// A JSON file lists versioned keys, each as base64 raw key bytes (the same
+/// encoding the TypeScript SDK's `verifySignature` accepts) or as PEM:
+///
+/// ```json
+/// {
+///   "keys": [
+///     { "keyVersion": 1, "publicKey": "base64..." },
+///     { "keyVersion": 2, "publicKey": "-----BEGIN PUBLIC KEY-----\n..." }
+///   ]
+/// }
+/// ```
+///
+/// # Example
+/// ```rust,no_run
+/// use truthlinked_sdk::{witness::WitnessKeyring, Client};
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("https://api.truthlinked.org", "key")?;
+/// let keyring = WitnessKeyring::from_json_file("/etc/truthlinked/witness-keys.json")?;
+/// let sth = client.get_latest_sth().await?;
+/// keyring.verify_sth(&sth)?;
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Default)]
+pub struct WitnessKeyring {
+    keys: BTreeMap<u32, VerifyingKey>,
+}
+
+#[derive(Deserialize)]
+struct KeyringFile {
+    keys: Vec<KeyringEntry>,
+}
+
+#[derive(Deserialize)]
+struct KeyringEntry {
+    #[serde(rename = "keyVersion")]
+    key_version: u32,
+    #[serde(rename = "publicKey")]
+    public_key: String,
+}
+
+impl WitnessKeyring {
+    /// Create an empty keyring
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Trust a raw 32-byte Ed25519 public key for `key_version`
+    pub fn add_key(&mut self, key_version: u32, public_key: &[u8]) -> Result<()> {
+        let bytes: [u8; 32] = public_key
+            .try_into()
+            .map_err(|_| invalid_key())?;
+        let key = VerifyingKey::from_bytes(&bytes).map_err(|_| invalid_key())?;
+        
+        self.keys.insert(key_version, key);
+        Ok(())
+    }
+    
+    /// Trust a PEM-encoded (SPKI) Ed25519 public key for `key_version`
+    pub fn add_pem(&mut self, key_version: u32, pem: &str) -> Result<()> {
+        let key = VerifyingKey::from_public_key_pem(pem.trim()).map_err(|_| invalid_key())?;
+        
+        self.keys.insert(key_version, key);
+        Ok(())
+    }
+    
+    /// Parse a JSON keyring document
+    pub fn from_json(json: &str) -> Result<Self> {
+        let file: KeyringFile = serde_json::from_str(json)
+            .map_err(|_| TruthlinkedError::InvalidRequest("Invalid keyring JSON".to_string()))?;
+        
+        let mut keyring = Self::new();
+        for entry in file.keys {
+            keyring.keys.insert(entry.key_version, decode_public_key(&entry.public_key)?);
+        }
+        
+        Ok(keyring)
+    }
+    
+    /// Load a JSON keyring document from disk
+    pub fn from_json_file(path: impl AsRef<Path>) -> Result<Self> {
+        Self::from_json(&read_key_file(path.as_ref())?)
+    }
+    
+    /// Load a single PEM public key from disk under `key_version`
+    pub fn from_pem_file(key_version: u32, path: impl AsRef<Path>) -> Result<Self> {
+        let mut keyring = Self::new();
+        keyring.add_pem(key_version, &read_key_file(path.as_ref())?)?;
+        Ok(keyring)
+    }
+    
+    /// Whether a key is trusted for `key_version`
+    pub fn contains(&self, key_version: u32) -> bool {
+        self.keys.contains_key(&key_version)
+    }
+    
+    /// Number of trusted key versions
+    pub fn len(&self) -> usize {
+        self.keys.len()
+    }
+    
+    /// Whether the keyring holds no keys
+    pub fn is_empty(&self) -> bool {
+        self.keys.is_empty()
+    }
+    
+    /// Verify a signed tree head's Ed25519 signature
+    ///
+    /// The signature (base64) must cover [`sth_signing_payload`] and be made
+    /// by the key trusted for the head's `key_version`.
+    pub fn verify_sth(&self, sth: &SignedTreeHead) -> std::result::Result<(), VerificationError> {
+        let key = self
+            .keys
+            .get(&sth.key_version)
+            .ok_or(VerificationError::UnknownKeyVersion(sth.key_version))?;
+        
+        let signature = base64::engine::general_purpose::STANDARD
+            .decode(sth.signature.trim())
+            .ok()
+            .and_then(|bytes| Signature::from_slice(&bytes).ok())
+            .ok_or(VerificationError::MalformedSignature)?;
+        
+        key.verify(&sth_signing_payload(sth), &signature)
+            .map_err(|_| VerificationError::InvalidSignature)
+    }
+}
+
+/// Canonical bytes covered by a tree head signature
+///
+/// The payload is the UTF-8 text `TREE_SIZE\nTIMESTAMP\nROOT_HASH`, with
+/// both integers in decimal and the root hash as lowercase hex.
+pub fn sth_signing_payload(sth: &SignedTreeHead) -> Vec<u8> {
+    format!(
+        "{}\n{}\n{}",
+        sth.tree_size,
+        sth.timestamp,
+        sth.root_hash.to_ascii_lowercase()
+    )
+    .into_bytes()
+}
+
+/// Decode an Ed25519 public key given as base64 raw bytes or as SPKI PEM
+pub(crate) fn decode_public_key(encoded: &str) -> Result<VerifyingKey> {
+    let encoded = encoded.trim();
+    if encoded.contains("-----BEGIN") {
+        return VerifyingKey::from_public_key_pem(encoded).map_err(|_| invalid_key());
+    }
+    
+    let bytes: [u8; 32] = base64::engine::general_purpose::STANDARD
+        .decode(encoded)
+        .map_err(|_| invalid_key())?
+        .try_into()
+        .map_err(|_| invalid_key())?;
+    VerifyingKey::from_bytes(&bytes).map_err(|_| invalid_key())
+}
+
+fn invalid_key() -> TruthlinkedError {
+    TruthlinkedError::InvalidRequest("Invalid Ed25519 public key".to_string())
+}
+
+fn read_key_file(path: &Path) -> Result<String> {
+    std::fs::read_to_string(path)
+        .map_err(|_| TruthlinkedError::InvalidRequest("Failed to read key file".to_string()))
+}
@@ -0,0 +1,231 @@
+use sha2::{Digest, Sha256};
+
+/// A 32-byte SHA-256 Merkle tree hash
+pub type Hash = [u8; 32];
+
+/// Domain separation prefix for leaf hashes (RFC 6962 section 2.1)
+const LEAF_PREFIX: u8 = 0x00;
+/// Domain separation prefix for interior node hashes (RFC 6962 section 2.1)
+const NODE_PREFIX: u8 = 0x01;
+
+/// Hash a leaf: `SHA-256(0x00 || data)`
+pub fn leaf_hash(data: &[u8]) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([LEAF_PREFIX]);
+    hasher.update(data);
+    hasher.finalize().into()
+}
+
+/// Hash an interior node: `SHA-256(0x01 || left || right)`
+pub fn node_hash(left: &Hash, right: &Hash) -> Hash {
+    let mut hasher = Sha256::new();
+    hasher.update([NODE_PREFIX]);
+    hasher.update(left);
+    hasher.update(right);
+    hasher.finalize().into()
+}
+
+/// Root of the empty tree: `SHA-256("")`
+pub fn empty_root() -> Hash {
+    Sha256::digest([]).into()
+}
+
+/// Incremental Merkle tree builder
+///
+/// Keeps only the roots of the perfect subtrees along the right edge of the
+/// tree (at most one per bit of the leaf count), so appending is O(log n)
+/// in time and memory. The root matches RFC 6962's Merkle Tree Hash.
+#[derive(Debug, Clone, Default)]
+pub struct MerkleAccumulator {
+    /// Perfect subtree roots, largest (leftmost) first, with their leaf counts
+    peaks: Vec<(Hash, u64)>,
+    size: u64,
+}
+
+impl MerkleAccumulator {
+    /// Create an empty accumulator
+    pub fn new() -> Self {
+        Self::default()
+    }
+    
+    /// Append a leaf hash (already prefixed, see [`leaf_hash`])
+    pub fn push(&mut self, leaf: Hash) {
+        let mut node = (leaf, 1u64);
+        while let Some(&(left, count)) = self.peaks.last() {
+            if count != node.1 {
+                break;
+            }
+            self.peaks.pop();
+            node = (node_hash(&left, &node.0), count * 2);
+        }
+        self.peaks.push(node);
+        self.size += 1;
+    }
+    
+    /// Number of leaves appended so far
+    pub fn len(&self) -> u64 {
+        self.size
+    }
+    
+    /// Whether no leaves have been appended
+    pub fn is_empty(&self) -> bool {
+        self.size == 0
+    }
+    
+    /// Current tree root
+    pub fn root(&self) -> Hash {
+        let mut peaks = self.peaks.iter().rev();
+        match peaks.next() {
+            Some(&(last, _)) => peaks.fold(last, |right, (left, _)| node_hash(left, &right)),
+            None => empty_root(),
+        }
+    }
+}
+
+/// Recompute the tree root from a leaf hash and its audit path
+///
+/// Implements the verification algorithm of RFC 9162 section 2.1.3.2.
+/// Returns `None` if the path is too short or too long for the given
+/// leaf index and tree size.
+pub(crate) fn root_from_inclusion_path(
+    leaf_index: u64,
+    tree_size: u64,
+    leaf: Hash,
+    path: &[Hash],
+) -> Option<Hash> {
+    if leaf_index >= tree_size {
+        return None;
+    }
+    
+    let mut fn_ = leaf_index;
+    let mut sn = tree_size - 1;
+    let mut root = leaf;
+    
+    for sibling in path {
+        if sn == 0 {
+            return None;
+        }
+        
+        if fn_ & 1 == 1 || fn_ == sn {
+            root = node_hash(sibling, &root);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            root = node_hash(&root, sibling);
+        }
+        
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    
+    if sn == 0 {
+        Some(root)
+    } else {
+        None
+    }
+}
+
+/// Recompute both tree roots from an RFC 9162 consistency path
+///
+/// Implements the verification algorithm of RFC 9162 section 2.1.4.2 for
+/// `0 < first_size < second_size`. Returns the `(first_root, second_root)`
+/// implied by the path, or `None` if the path does not have the right shape.
+pub(crate) fn roots_from_consistency_path(
+    first_size: u64,
+    second_size: u64,
+    first_root: Hash,
+    path: &[Hash],
+) -> Option<(Hash, Hash)> {
+    if first_size == 0 || first_size >= second_size {
+        return None;
+    }
+    
+    // If the old tree is a complete subtree its root is the first node
+    let mut nodes = Vec::with_capacity(path.len() + 1);
+    if first_size.is_power_of_two() {
+        nodes.push(first_root);
+    }
+    nodes.extend_from_slice(path);
+    
+    let (&seed, rest) = nodes.split_first()?;
+    
+    let mut fn_ = first_size - 1;
+    let mut sn = second_size - 1;
+    while fn_ & 1 == 1 {
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    
+    let mut fr = seed;
+    let mut sr = seed;
+    
+    for node in rest {
+        if sn == 0 {
+            return None;
+        }
+        
+        if fn_ & 1 == 1 || fn_ == sn {
+            fr = node_hash(node, &fr);
+            sr = node_hash(node, &sr);
+            while fn_ & 1 == 0 && fn_ != 0 {
+                fn_ >>= 1;
+                sn >>= 1;
+            }
+        } else {
+            sr = node_hash(&sr, node);
+        }
+        
+        fn_ >>= 1;
+        sn >>= 1;
+    }
+    
+    if sn == 0 {
+        Some((fr, sr))
+    } else {
+        None
+    }
+}
+
+/// Decode a hex-encoded 32-byte hash
+pub(crate) fn decode_hash(value: &str) -> Option<Hash> {
+    let bytes = hex::decode(value).ok()?;
+    bytes.try_into().ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    
+    #[test]
+    fn test_rfc6962_empty_leaf_hash() {
+        // Leaf hash of the empty string from the RFC 6962 test vectors
+        assert_eq!(
+            hex::encode(leaf_hash(b"")),
+            "6e340b9cffb37a989ca544e6bb780a2c78901d3fb33738768511a30617afa01d"
+        );
+    }
+    
+    #[test]
+    fn test_accumulator_matches_manual_tree() {
+        let leaves: Vec<Hash> = (0u8..3).map(|i| leaf_hash(&[i])).collect();
+        let mut accumulator = MerkleAccumulator::new();
+        
+        assert_eq!(accumulator.root(), empty_root());
+        for leaf in &leaves {
+            accumulator.push(*leaf);
+        }
+        
+        let expected = node_hash(&node_hash(&leaves[0], &leaves[1]), &leaves[2]);
+        assert_eq!(accumulator.len(), 3);
+        assert_eq!(accumulator.root(), expected);
+    }
+    
+    #[test]
+    fn test_single_leaf_tree() {
+        let leaf = leaf_hash(b"event");
+        assert_eq!(root_from_inclusion_path(0, 1, leaf, &[]), Some(leaf));
+        assert_eq!(root_from_inclusion_path(1, 1, leaf, &[]), None);
+    }
+}
@@ -0,0 +1,285 @@
+//! Client-side verification for the witness chain
+//!
+//! The witness endpoints on [`Client`](crate::Client) return data exactly as
+//! the server reports it. The functions in this module let auditors check
+//! those responses locally instead of trusting the server.
+//!
+//! # Merkle Tree Construction
+//! The witness log is an RFC 6962 / RFC 9162 Merkle tree over SHA-256:
+//! - Each leaf is the raw 32-byte `event_hash` of a [`WitnessEvent`]
+//!   (hex-decoded), hashed as `SHA-256(0x00 || event_hash)`
+//! - Interior nodes are hashed as `SHA-256(0x01 || left || right)`
+//! - [`SignedTreeHead::root_hash`] is the hex-encoded root of the first
+//!   `tree_size` leaves
+//!
+//! # Verifying Append-Only Growth
+//! [`verify_consistency`] checks a consistency proof from
+//! [`Client::get_consistency_proof`](crate::Client::get_consistency_proof),
+//! proving that an older tree head is a prefix of a newer one. A failure
+//! means the log was forked or rewritten between the two heads.
+//!
+//! # Offline Export Verification
+//! [`parse_export`] reads the bundles returned by
+//! [`Client::export_witness_chain`](crate::Client::export_witness_chain) and
+//! [`verify_export`] checks them end to end without network access: hash
+//! chain, sequence continuity, event hashes and the final Merkle root.
+//!
+//! # Continuous Monitoring
+//! [`WitnessMonitor`] polls the log in the background, checks each new
+//! head for consistency with the last accepted one and raises
+//! [`MonitorEvent`] alerts on rollbacks, split views, stale heads and a
+//! shrinking chain.
+//!
+//! # Building Submissions
+//! [`SubmissionBuilder`] turns raw Authority Fabric events into signed
+//! [`WitnessSubmission`](crate::WitnessSubmission)s, computing the event
+//! hash, Merkle root and sequence number locally.
+//!
+//! # Tree Head Signatures
+//! A [`WitnessKeyring`] holds the trusted Ed25519 keys for each
+//! `key_version` and checks [`SignedTreeHead::signature`]. Passing a keyring
+//! to [`ClientBuilder::witness_keyring`](crate::ClientBuilder::witness_keyring)
+//! makes `get_latest_sth` and `get_sth` refuse heads that do not verify.
+//!
+//! # Example
+//! ```rust,no_run
+//! use truthlinked_sdk::{witness, Client};
+//!
+//! # #[tokio::main]
+//! # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+//! # let client = Client::new("https://api.truthlinked.org", "key")?;
+//! let sth = client.get_latest_sth().await?;
+//! let event = client.get_witness_event(42, true).await?;
+//!
+//! witness::verify_inclusion(&event, &sth)?;
+//! println!("Event 42 is included in the tree of size {}", sth.tree_size);
+//! # Ok(())
+//! # }
+//! ```
+
+mod export;
+pub(crate) mod keyring;
+mod merkle;
+mod monitor;
+mod submission;
+
+pub use export::{
+    compute_event_hash, parse_export, verify_export, ChainVerifier, ExportReader, ExportSummary,
+    GENESIS_PREV_HASH,
+};
+pub use keyring::{sth_signing_payload, WitnessKeyring};
+pub use merkle::{empty_root, leaf_hash, node_hash, Hash, MerkleAccumulator};
+pub use monitor::{
+    FileSthStore, MemorySthStore, MonitorEvent, SthStore, WitnessMonitor, DEFAULT_STORE_PATH,
+};
+pub use submission::{submission_signing_payload, SubmissionBuilder};
+
+use crate::error::VerificationError;
+use crate::types::{ConsistencyProof, SignedTreeHead, WitnessEvent};
+use serde::{Deserialize, Serialize};
+
+/// Merkle audit path proving a single event is included in the witness log
+///
+/// This is the decoded form of [`WitnessEvent::proof`], which the server
+/// encodes as a JSON object:
+///
+/// ```json
+/// { "leafIndex": 42, "treeSize": 128, "auditPath": ["<hex>", "..."] }
+/// ```
+///
+/// `auditPath` lists sibling hashes from the leaf up to the root, as in
+/// RFC 9162 section 2.1.3.
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub struct InclusionProof {
+    /// Zero-based index of the leaf in the tree
+    #[serde(rename = "leafIndex")]
+    pub leaf_index: u64,
+    /// Size of the tree the proof was generated against
+    #[serde(rename = "treeSize")]
+    pub tree_size: u64,
+    /// Hex-encoded sibling hashes, leaf to root
+    #[serde(rename = "auditPath")]
+    pub audit_path: Vec<String>,
+}
+
+impl InclusionProof {
+    /// Parse the proof string carried in [`WitnessEvent::proof`]
+    pub fn parse(proof: &str) -> Result<Self, VerificationError> {
+        serde_json::from_str(proof).map_err(|_| VerificationError::MalformedProof)
+    }
+}
+
+/// Verify that a witness event is included in the tree described by a signed tree head
+///
+/// The event must have been fetched with `include_proof: true`. Verification
+/// recomputes the root from the event's `event_hash` and the audit path and
+/// compares it against `sth.root_hash`.
+///
+/// This does not check the tree head's signature; verify the head with a
+/// [`WitnessKeyring`] before relying on its root hash.
+///
+/// # Errors
+/// Returns the [`VerificationError`] describing the first step that failed.
+pub fn verify_inclusion(event: &WitnessEvent, sth: &SignedTreeHead) -> Result<(), VerificationError> {
+    let proof = event.proof.as_deref().ok_or(VerificationError::MissingProof)?;
+    let proof = InclusionProof::parse(proof)?;
+    
+    verify_inclusion_proof(&event.event_hash, &proof, sth)
+}
+
+/// Verify an already-decoded inclusion proof for `event_hash` against a signed tree head
+pub fn verify_inclusion_proof(
+    event_hash: &str,
+    proof: &InclusionProof,
+    sth: &SignedTreeHead,
+) -> Result<(), VerificationError> {
+    if proof.tree_size != sth.tree_size {
+        return Err(VerificationError::TreeSizeMismatch {
+            proof: proof.tree_size,
+            tree_head: sth.tree_size,
+        });
+    }
+    
+    if proof.leaf_index >= proof.tree_size {
+        return Err(VerificationError::LeafIndexOutOfRange {
+            leaf_index: proof.leaf_index,
+            tree_size: proof.tree_size,
+        });
+    }
+    
+    let event_hash = decode_field(event_hash, "event_hash")?;
+    let expected_root = decode_field(&sth.root_hash, "root_hash")?;
+    let path = proof
+        .audit_path
+        .iter()
+        .map(|hash| decode_field(hash, "audit_path"))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    let computed = merkle::root_from_inclusion_path(
+        proof.leaf_index,
+        proof.tree_size,
+        leaf_hash(&event_hash),
+        &path,
+    )
+    .ok_or(VerificationError::InvalidPathLength {
+        leaf_index: proof.leaf_index,
+        tree_size: proof.tree_size,
+        path_length: path.len(),
+    })?;
+    
+    if computed != expected_root {
+        return Err(VerificationError::RootMismatch {
+            expected: sth.root_hash.clone(),
+            computed: hex::encode(computed),
+        });
+    }
+    
+    Ok(())
+}
+
+/// Verify that `old` is a prefix of `new` using a consistency proof
+///
+/// Both tree heads must be the ones the proof was requested for. Two heads of
+/// the same size are consistent only if their roots are identical; a head of
+/// size zero is consistent with every later head.
+///
+/// As with [`verify_inclusion`], this does not check tree head signatures.
+///
+/// # Example
+/// ```rust,no_run
+/// # use truthlinked_sdk::{witness, Client};
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// # let client = Client::new("https://api.truthlinked.org", "key")?;
+/// let old = client.get_sth(1024).await?;
+/// let new = client.get_latest_sth().await?;
+///
+/// let proof = client.get_consistency_proof(old.tree_size, new.tree_size).await?;
+/// witness::verify_consistency(&old, &new, &proof)?;
+/// # Ok(())
+/// # }
+/// ```
+///
+/// # Errors
+/// Returns the [`VerificationError`] describing the first step that failed.
+pub fn verify_consistency(
+    old: &SignedTreeHead,
+    new: &SignedTreeHead,
+    proof: &ConsistencyProof,
+) -> Result<(), VerificationError> {
+    if new.tree_size < old.tree_size {
+        return Err(VerificationError::TreeShrunk {
+            old: old.tree_size,
+            new: new.tree_size,
+        });
+    }
+    
+    if proof.first_size != old.tree_size {
+        return Err(VerificationError::TreeSizeMismatch {
+            proof: proof.first_size,
+            tree_head: old.tree_size,
+        });
+    }
+    if proof.second_size != new.tree_size {
+        return Err(VerificationError::TreeSizeMismatch {
+            proof: proof.second_size,
+            tree_head: new.tree_size,
+        });
+    }
+    
+    let old_root = decode_field(&old.root_hash, "root_hash")?;
+    let new_root = decode_field(&new.root_hash, "root_hash")?;
+    let path = proof
+        .proof
+        .iter()
+        .map(|hash| decode_field(hash, "proof"))
+        .collect::<Result<Vec<_>, _>>()?;
+    
+    // An empty tree is a prefix of every tree
+    if old.tree_size == 0 {
+        return Ok(());
+    }
+    
+    // Same size: the heads must describe the same tree
+    if old.tree_size == new.tree_size {
+        if !path.is_empty() {
+            return Err(VerificationError::InvalidConsistencyPath);
+        }
+        if old_root != new_root {
+            return Err(VerificationError::RootMismatch {
+                expected: old.root_hash.clone(),
+                computed: new.root_hash.clone(),
+            });
+        }
+        return Ok(());
+    }
+    
+    let (computed_old, computed_new) = merkle::roots_from_consistency_path(
+        old.tree_size,
+        new.tree_size,
+        old_root,
+        &path,
+    )
+    .ok_or(VerificationError::InvalidConsistencyPath)?;
+    
+    if computed_old != old_root {
+        return Err(VerificationError::OldRootMismatch {
+            expected: old.root_hash.clone(),
+            computed: hex::encode(computed_old),
+        });
+    }
+    
+    if computed_new != new_root {
+        return Err(VerificationError::RootMismatch {
+            expected: new.root_hash.clone(),
+            computed: hex::encode(computed_new),
+        });
+    }
+    
+    Ok(())
+}
+
+/// Decode a hex hash field, naming the field on failure
+fn decode_field(value: &str, field: &'static str) -> Result<Hash, VerificationError> {
+    merkle::decode_hash(value).ok_or(VerificationError::InvalidHash(field))
+}
@@ -0,0 +1,385 @@
+use super::verify_consistency;
+use crate::client::Client;
+use crate::error::{Result, TruthlinkedError, VerificationError};
+use crate::signing::RequestSigner;
+use crate::types::SignedTreeHead;
+use std::path::PathBuf;
+use std::sync::{Arc, Mutex};
+use std::time::Duration;
+use tokio::sync::mpsc;
+use tokio::task::JoinHandle;
+
+/// File used by [`WitnessMonitor::new`], relative to the working directory
+pub const DEFAULT_STORE_PATH: &str = "witness-head.json";
+
+/// Persistent storage for the last accepted signed tree head
+///
+/// The monitor only ever moves forward from the head in the store, so the
+/// store is what makes rollback and fork detection survive restarts.
+pub trait SthStore: Send + Sync {
+    /// Load the last accepted head, if any
+    fn load(&self) -> Result<Option<SignedTreeHead>>;
+    
+    /// Persist a newly accepted head
+    fn save(&self, sth: &SignedTreeHead) -> Result<()>;
+}
+
+/// Stores the last accepted head as a JSON file
+///
+/// Writes go to a temporary file that is renamed over the target, so a
+/// crash mid-write never leaves a truncated head behind.
+#[derive(Debug, Clone)]
+pub struct FileSthStore {
+    path: PathBuf,
+}
+
+impl FileSthStore {
+    /// Store heads at `path` (created on first save)
+    pub fn new(path: impl Into<PathBuf>) -> Self {
+        Self { path: path.into() }
+    }
+}
+
+impl SthStore for FileSthStore {
+    fn load(&self) -> Result<Option<SignedTreeHead>> {
+        match std::fs::read(&self.path) {
+            Ok(bytes) => Ok(Some(serde_json::from_slice(&bytes)?)),
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => Ok(None),
+            Err(_) => Err(TruthlinkedError::Storage("Failed to read tree head file".to_string())),
+        }
+    }
+    
+    fn save(&self, sth: &SignedTreeHead) -> Result<()> {
+        // Appended rather than replacing the extension, so it never equals the target
+        let mut temp = self.path.clone().into_os_string();
+        temp.push(".tmp");
+        let temp = PathBuf::from(temp);
+        std::fs::write(&temp, serde_json::to_vec_pretty(sth)?)
+            .and_then(|_| std::fs::rename(&temp, &self.path))
+            .map_err(|_| TruthlinkedError::Storage("Failed to write tree head file".to_string()))
+    }
+}
+
+/// Keeps the last accepted head in memory (useful for tests and short-lived monitors)
+#[derive(Debug, Default)]
+pub struct MemorySthStore {
+    head: Mutex<Option<SignedTreeHead>>,
+}
+
+impl MemorySthStore {
+    /// Create an empty store
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl SthStore for MemorySthStore {
+    fn load(&self) -> Result<Option<SignedTreeHead>> {
+        Ok(self.head.lock().unwrap_or_else(|e| e.into_inner()).clone())
+    }
+    
+    fn save(&self, sth: &SignedTreeHead) -> Result<()> {
+        *self.head.lock().unwrap_or_else(|e| e.into_inner()) = Some(sth.clone());
+        Ok(())
+    }
+}
+
+/// Something the monitor observed
+///
+/// Every variant other than [`HeadAccepted`](Self::HeadAccepted) is an
+/// alert; alerts are also logged at `warn` level.
+#[derive(Debug)]
+pub enum MonitorEvent {
+    /// A new head was verified consistent with the trusted one and persisted
+    HeadAccepted(SignedTreeHead),
+    /// Two heads of the same size have different roots
+    SplitView {
+        trusted: SignedTreeHead,
+        observed: SignedTreeHead,
+    },
+    /// A head is smaller than the trusted head
+    Rollback {
+        trusted: SignedTreeHead,
+        observed: SignedTreeHead,
+    },
+    /// A larger head failed the consistency check against the trusted head
+    ConsistencyFailure {
+        trusted: SignedTreeHead,
+        observed: SignedTreeHead,
+        error: VerificationError,
+    },
+    /// A larger head carries an older timestamp than the trusted head
+    TimestampRegression {
+        trusted: SignedTreeHead,
+        observed: SignedTreeHead,
+    },
+    /// The latest head is older than the configured maximum age
+    StaleHead {
+        sth: SignedTreeHead,
+        age: Duration,
+    },
+    /// The witness health endpoint reported a smaller chain than before
+    ChainShrunk { previous: u64, current: u64 },
+    /// A head could not be fetched or verified
+    FetchFailed(TruthlinkedError),
+    /// The head store could not be read or written
+    StoreFailed(TruthlinkedError),
+}
+
+impl MonitorEvent {
+    /// Whether this event indicates possible tampering or an operational problem
+    pub fn is_alert(&self) -> bool {
+        !matches!(self, MonitorEvent::HeadAccepted(_))
+    }
+}
+
+/// Background auditor for the witness log
+///
+/// Each round the monitor:
+/// 1. Checks `witness_health` and alerts if `chain_size` shrank
+/// 2. Fetches the latest signed tree head (verified against the client's
+///    witness keyring, if one is configured) and alerts if it is stale
+/// 3. Compares it to the last accepted head from the [`SthStore`]:
+///    smaller heads are rollbacks, equal-size heads with different roots are
+///    split views, and larger heads must pass a consistency proof
+/// 4. Persists the head once it has been accepted
+///
+/// With an empty store the first head is trusted on first use; seed the
+/// store with a known-good head to avoid that.
+///
+/// Heads obtained from other parties (gossip) can be cross-checked with
+/// [`check_gossip`](Self::check_gossip).
+///
+/// # Example
+/// ```rust,no_run
+/// use std::sync::Arc;
+/// use std::time::Duration;
+/// use truthlinked_sdk::witness::{FileSthStore, WitnessMonitor};
+/// use truthlinked_sdk::Client;
+///
+/// # #[tokio::main]
+/// # async fn main() -> Result<(), Box<dyn std::error::Error>> {
+/// let client = Arc::new(Client::new("https://api.truthlinked.org", "key")?);
+///
+/// let (_task, mut events) = WitnessMonitor::new(client)
+///     .store(FileSthStore::new("/var/lib/myapp/witness-head.json"))
+///     .poll_interval(Duration::from_secs(60))
+///     .spawn();
+///
+/// while let Some(event) = events.recv().await {
+///     if event.is_alert() {
+///         eprintln!("Witness alert: {:?}", event);
+///     }
+/// }
+/// # Ok(())
+/// # }
+/// ```
+pub struct WitnessMonitor {
+    client: Arc<Client>,
+    store: Box<dyn SthStore>,
+    poll_interval: Duration,
+    max_head_age: Option<Duration>,
+    last_chain_size: Option<u64>,
+}
+
+impl WitnessMonitor {
+    /// Create a monitor polling every 5 minutes with no staleness limit
+    ///
+    /// Accepted heads are persisted to [`DEFAULT_STORE_PATH`] unless another
+    /// [`store`](Self::store) is set.
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            store: Box::new(FileSthStore::new(DEFAULT_STORE_PATH)),
+            poll_interval: Duration::from_secs(300),
+            max_head_age: None,
+            last_chain_size: None,
+        }
+    }
+    
+    /// Persist accepted heads in `store` instead of the default file
+    pub fn store(mut self, store: impl SthStore + 'static) -> Self {
+        self.store = Box::new(store);
+        self
+    }
+    
+    /// Set how often the background task polls
+    pub fn poll_interval(mut self, interval: Duration) -> Self {
+        self.poll_interval = interval;
+        self
+    }
+    
+    /// Alert when the latest head's timestamp is older than `max_age`
+    pub fn max_head_age(mut self, max_age: Duration) -> Self {
+        self.max_head_age = Some(max_age);
+        self
+    }
+    
+    /// Run one monitoring round and return what was observed
+    pub async fn check(&mut self) -> Vec<MonitorEvent> {
+        let mut events = Vec::new();
+        
+        match self.client.witness_health().await {
+            Ok(health) => {
+                if let Some(previous) = self.last_chain_size {
+                    if health.chain_size < previous {
+                        events.push(MonitorEvent::ChainShrunk {
+                            previous,
+                            current: health.chain_size,
+                        });
+                    }
+                }
+                self.last_chain_size = Some(health.chain_size);
+            }
+            Err(e) => events.push(MonitorEvent::FetchFailed(e)),
+        }
+        
+        match self.client.get_latest_sth().await {
+            Ok(latest) => {
+                if let Some(event) = self.check_staleness(&latest) {
+                    events.push(event);
+                }
+                self.observe(latest, &mut events).await;
+            }
+            Err(e) => events.push(MonitorEvent::FetchFailed(e)),
+        }
+        
+        log_alerts(&events);
+        events
+    }
+    
+    /// Cross-check a head obtained from another party against the log
+    ///
+    /// If the gossiped head is no larger than the trusted head, the server's
+    /// head at that size must have the same root; otherwise it must be
+    /// consistent with the trusted head. Gossiped heads are never persisted.
+    pub async fn check_gossip(&mut self, gossiped: &SignedTreeHead) -> Vec<MonitorEvent> {
+        let mut events = Vec::new();
+        
+        let trusted = match self.store.load() {
+            Ok(Some(trusted)) => trusted,
+            Ok(None) => return events,
+            Err(e) => {
+                events.push(MonitorEvent::StoreFailed(e));
+                return events;
+            }
+        };
+        
+        if gossiped.tree_size <= trusted.tree_size {
+            match self.client.get_sth(gossiped.tree_size).await {
+                Ok(ours) if !ours.root_hash.eq_ignore_ascii_case(&gossiped.root_hash) => {
+                    events.push(MonitorEvent::SplitView {
+                        trusted: ours,
+                        observed: gossiped.clone(),
+                    });
+                }
+                Ok(_) => {}
+                Err(e) => events.push(MonitorEvent::FetchFailed(e)),
+            }
+        } else if let Err(event) = self.verify_growth(&trusted, gossiped).await {
+            events.push(event);
+        }
+        
+        log_alerts(&events);
+        events
+    }
+    
+    /// Spawn the monitor as a background task
+    ///
+    /// Events are delivered on the returned channel. The task stops when the
+    /// receiver is dropped or the handle is aborted.
+    pub fn spawn(mut self) -> (JoinHandle<()>, mpsc::Receiver<MonitorEvent>) {
+        let (sender, receiver) = mpsc::channel(64);
+        
+        let handle = tokio::spawn(async move {
+            let mut interval = tokio::time::interval(self.poll_interval);
+            interval.set_missed_tick_behavior(tokio::time::MissedTickBehavior::Delay);
+            
+            loop {
+                interval.tick().await;
+                for event in self.check().await {
+                    if sender.send(event).await.is_err() {
+                        return;
+                    }
+                }
+            }
+        });
+        
+        (handle, receiver)
+    }
+    
+    /// Compare the latest head with the trusted one and accept it if valid
+    async fn observe(&mut self, latest: SignedTreeHead, events: &mut Vec<MonitorEvent>) {
+        let trusted = match self.store.load() {
+            Ok(trusted) => trusted,
+            Err(e) => {
+                events.push(MonitorEvent::StoreFailed(e));
+                return;
+            }
+        };
+        
+        if let Some(trusted) = trusted {
+            if latest.tree_size < trusted.tree_size {
+                events.push(MonitorEvent::Rollback { trusted, observed: latest });
+                return;
+            }
+            
+            if latest.tree_size == trusted.tree_size {
+                if !latest.root_hash.eq_ignore_ascii_case(&trusted.root_hash) {
+                    events.push(MonitorEvent::SplitView { trusted, observed: latest });
+                }
+                return;
+            }
+            
+            if let Err(event) = self.verify_growth(&trusted, &latest).await {
+                events.push(event);
+                return;
+            }
+        }
+        
+        match self.store.save(&latest) {
+            Ok(()) => events.push(MonitorEvent::HeadAccepted(latest)),
+            Err(e) => events.push(MonitorEvent::StoreFailed(e)),
+        }
+    }
+    
+    /// Check that a larger head extends the trusted one
+    async fn verify_growth(
+        &self,
+        trusted: &SignedTreeHead,
+        observed: &SignedTreeHead,
+    ) -> std::result::Result<(), MonitorEvent> {
+        if observed.timestamp < trusted.timestamp {
+            return Err(MonitorEvent::TimestampRegression {
+                trusted: trusted.clone(),
+                observed: observed.clone(),
+            });
+        }
+        
+        let proof = self
+            .client
+            .get_consistency_proof(trusted.tree_size, observed.tree_size)
+            .await
+            .map_err(MonitorEvent::FetchFailed)?;
+        
+        verify_consistency(trusted, observed, &proof).map_err(|error| MonitorEvent::ConsistencyFailure {
+            trusted: trusted.clone(),
+            observed: observed.clone(),
+            error,
+        })
+    }
+    
+    fn check_staleness(&self, sth: &SignedTreeHead) -> Option<MonitorEvent> {
+        let max_age = self.max_head_age?;
+        let now = RequestSigner::current_timestamp();
+        let age = Duration::from_secs(now.saturating_sub(sth.timestamp));
+        
+        (age > max_age).then(|| MonitorEvent::StaleHead { sth: sth.clone(), age })
+    }
+}
+
+fn log_alerts(events: &[MonitorEvent]) {
+    for event in events.iter().filter(|event| event.is_alert()) {
+        tracing::warn!(event = ?event, "Witness monitor alert");
+    }
+}
@@ -0,0 +1,165 @@
+use truthlinked_sdk::middleware::{AfPrincipal, FailurePolicy, RequireScope, TokenValidator, TruthlinkedAuth, ValidateFuture};
+use truthlinked_sdk::*;
+use actix_web::http::{header, StatusCode};
+use actix_web::{test, web, App};
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::sync::Arc;
+
+/// Accepts "good" with read scope, "admin" with read and write scope
+struct StaticValidator;
+
+impl TokenValidator for StaticValidator {
+    fn validate<'a>(&'a self, token: &'a str) -> ValidateFuture<'a> {
+        Box::pin(async move {
+            let scope = match token {
+                "good" => vec!["read:users"],
+                "admin" => vec!["read:users", "write:users"],
+                "down" => return Err(TruthlinkedError::Network("connection refused".to_string())),
+                _ => return Ok(ValidateResponse { valid: false, subject: None, scope: None }),
+            };
+            Ok(ValidateResponse {
+                valid: true,
+                subject: Some(token.to_string()),
+                scope: Some(scope.into_iter().map(String::from).collect()),
+            })
+        })
+    }
+}
+
+async fn whoami(principal: AfPrincipal) -> String {
+    principal.subject.unwrap_or_else(|| "anonymous".to_string())
+}
+
+async fn call(auth: TruthlinkedAuth, path: &str, token: Option<&str>) -> (StatusCode, Option<String>, String) {
+    let app = test::init_service(
+        App::new()
+            .app_data(auth)
+            .route("/users", web::get().to(whoami))
+            .service(
+                web::resource("/admin")
+                    .wrap(RequireScope::new("read:users").and("write:users"))
+                    .route(web::get().to(whoami)),
+            ),
+    )
+    .await;
+    
+    let mut request = test::TestRequest::get().uri(path);
+    if let Some(token) = token {
+        request = request.insert_header((header::AUTHORIZATION, format!("Bearer {}", token)));
+    }
+    
+    let response = test::call_service(&app, request.to_request()).await;
+    let status = response.status();
+    let challenge = response
+        .headers()
+        .get(header::WWW_AUTHENTICATE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = test::read_body(response).await;
+    
+    (status, challenge, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[cfg(test)]
+mod actix_middleware_tests {
+    use super::*;
+    
+    fn static_auth() -> TruthlinkedAuth {
+        TruthlinkedAuth::new(Arc::new(StaticValidator))
+    }
+    
+    #[actix_rt::test]
+    async fn test_extractor_valid_token() {
+        let (status, _, body) = call(static_auth(), "/users", Some("good")).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "good");
+    }
+    
+    #[actix_rt::test]
+    async fn test_extractor_missing_token() {
+        let (status, challenge, _) = call(static_auth(), "/users", None).await;
+        
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(challenge.as_deref(), Some("Bearer"));
+    }
+    
+    #[actix_rt::test]
+    async fn test_extractor_invalid_token() {
+        let (status, _, body) = call(static_auth(), "/users", Some("forged")).await;
+        
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(body, r#"{"error":"invalid_token"}"#);
+    }
+    
+    #[actix_rt::test]
+    async fn test_require_scope() {
+        let (status, challenge, _) = call(static_auth(), "/admin", Some("good")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(
+            challenge.as_deref(),
+            Some("Bearer error=\"insufficient_scope\", scope=\"read:users write:users\"")
+        );
+        
+        let (status, _, body) = call(static_auth(), "/admin", Some("admin")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "admin");
+    }
+    
+    #[actix_rt::test]
+    async fn test_network_failure_closed_by_default() {
+        let (status, _, _) = call(static_auth(), "/admin", Some("down")).await;
+        
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+    
+    #[actix_rt::test]
+    async fn test_network_failure_open() {
+        let auth = static_auth().failure_policy(FailurePolicy::FailOpen);
+        
+        let (status, _, body) = call(auth.clone(), "/users", Some("down")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "anonymous");
+    }
+    
+    #[actix_rt::test]
+    async fn test_network_failure_open_keeps_scope() {
+        let auth = static_auth().failure_policy(FailurePolicy::FailOpen);
+        
+        let (status, _, _) = call(auth, "/admin", Some("down")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+    
+    #[actix_rt::test]
+    async fn test_missing_app_data() {
+        let app = test::init_service(App::new().route("/users", web::get().to(whoami))).await;
+        let request = test::TestRequest::get()
+            .uri("/users")
+            .insert_header((header::AUTHORIZATION, "Bearer good"))
+            .to_request();
+        
+        let response = test::call_service(&app, request).await;
+        assert_eq!(response.status(), StatusCode::INTERNAL_SERVER_ERROR);
+    }
+    
+    #[actix_rt::test]
+    async fn test_validates_with_shared_client() {
+        let mut server = Server::new_async().await;
+        let validate = server.mock("POST", "/v1/validate")
+            .match_body(Matcher::Json(json!({ "token": "af_good" })))
+            .with_status(200)
+            .with_body(r#"{"valid":true,"subject":"alice","scope":["read:users","write:users"]}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let auth = TruthlinkedAuth::new(Arc::new(client));
+        
+        // Middleware and extractor share one validation
+        let (status, _, body) = call(auth, "/admin", Some("af_good")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "alice");
+        validate.assert_async().await;
+    }
+}
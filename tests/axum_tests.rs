@@ -0,0 +1,161 @@
+use truthlinked_sdk::middleware::{AfPrincipal, FailurePolicy, TokenValidator, TruthlinkedAuthLayer, ValidateFuture};
+use truthlinked_sdk::*;
+use axum::body::Body;
+use axum::http::{header, Request, StatusCode};
+use axum::routing::get;
+use axum::Router;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::sync::Arc;
+use tower::ServiceExt;
+
+/// Accepts "good" with read scope, "admin" with read and write scope
+struct StaticValidator;
+
+impl TokenValidator for StaticValidator {
+    fn validate<'a>(&'a self, token: &'a str) -> ValidateFuture<'a> {
+        Box::pin(async move {
+            let scope = match token {
+                "good" => vec!["read:users"],
+                "admin" => vec!["read:users", "write:users"],
+                "down" => return Err(TruthlinkedError::Network("connection refused".to_string())),
+                _ => return Ok(ValidateResponse { valid: false, subject: None, scope: None }),
+            };
+            Ok(ValidateResponse {
+                valid: true,
+                subject: Some(token.to_string()),
+                scope: Some(scope.into_iter().map(String::from).collect()),
+            })
+        })
+    }
+}
+
+async fn whoami(principal: AfPrincipal) -> String {
+    principal.subject.unwrap_or_else(|| "anonymous".to_string())
+}
+
+fn app(auth: TruthlinkedAuthLayer) -> Router {
+    Router::new()
+        .route("/users", get(whoami))
+        .route("/admin", get(whoami).route_layer(auth.require_scope("write:users")))
+        .layer(auth)
+}
+
+async fn call(app: Router, path: &str, token: Option<&str>) -> (StatusCode, Option<String>, String) {
+    let mut request = Request::builder().uri(path);
+    if let Some(token) = token {
+        request = request.header(header::AUTHORIZATION, format!("Bearer {}", token));
+    }
+    
+    let response = app.oneshot(request.body(Body::empty()).unwrap()).await.unwrap();
+    let status = response.status();
+    let challenge = response
+        .headers()
+        .get(header::WWW_AUTHENTICATE)
+        .map(|value| value.to_str().unwrap().to_string());
+    let body = axum::body::to_bytes(response.into_body(), usize::MAX).await.unwrap();
+    
+    (status, challenge, String::from_utf8(body.to_vec()).unwrap())
+}
+
+#[cfg(test)]
+mod layer_tests {
+    use super::*;
+    
+    fn static_app() -> Router {
+        app(TruthlinkedAuthLayer::new(Arc::new(StaticValidator)))
+    }
+    
+    #[tokio::test]
+    async fn test_valid_token_injects_principal() {
+        let (status, _, body) = call(static_app(), "/users", Some("good")).await;
+        
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "good");
+    }
+    
+    #[tokio::test]
+    async fn test_missing_token_401() {
+        let (status, challenge, _) = call(static_app(), "/users", None).await;
+        
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(challenge.as_deref(), Some("Bearer"));
+    }
+    
+    #[tokio::test]
+    async fn test_invalid_token_401() {
+        let (status, challenge, body) = call(static_app(), "/users", Some("forged")).await;
+        
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+        assert_eq!(challenge.as_deref(), Some("Bearer error=\"invalid_token\""));
+        assert_eq!(body, r#"{"error":"invalid_token"}"#);
+    }
+    
+    #[tokio::test]
+    async fn test_route_scope_enforced() {
+        let (status, challenge, _) = call(static_app(), "/admin", Some("good")).await;
+        assert_eq!(status, StatusCode::FORBIDDEN);
+        assert_eq!(
+            challenge.as_deref(),
+            Some("Bearer error=\"insufficient_scope\", scope=\"write:users\"")
+        );
+        
+        let (status, _, body) = call(static_app(), "/admin", Some("admin")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "admin");
+    }
+    
+    #[tokio::test]
+    async fn test_network_failure_closed_by_default() {
+        let (status, _, _) = call(static_app(), "/users", Some("down")).await;
+        
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+    
+    #[tokio::test]
+    async fn test_network_failure_open() {
+        let auth = TruthlinkedAuthLayer::new(Arc::new(StaticValidator))
+            .failure_policy(FailurePolicy::FailOpen);
+        
+        let app = app(auth);
+        
+        let (status, _, body) = call(app.clone(), "/users", Some("down")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "anonymous");
+    }
+    
+    #[tokio::test]
+    async fn test_network_failure_open_keeps_scope() {
+        let auth = TruthlinkedAuthLayer::new(Arc::new(StaticValidator))
+            .failure_policy(FailurePolicy::FailOpen);
+        
+        let (status, _, _) = call(app(auth), "/admin", Some("down")).await;
+        assert_eq!(status, StatusCode::SERVICE_UNAVAILABLE);
+    }
+    
+    #[tokio::test]
+    async fn test_validates_with_client() {
+        let mut server = Server::new_async().await;
+        let _valid = server.mock("POST", "/v1/validate")
+            .match_body(Matcher::Json(json!({ "token": "af_good" })))
+            .with_status(200)
+            .with_body(r#"{"valid":true,"subject":"alice","scope":["read:users"]}"#)
+            .create_async()
+            .await;
+        let _revoked = server.mock("POST", "/v1/validate")
+            .match_body(Matcher::Json(json!({ "token": "af_revoked" })))
+            .with_status(401)
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let app = app(TruthlinkedAuthLayer::new(Arc::new(client)));
+        
+        let (status, _, body) = call(app.clone(), "/users", Some("af_good")).await;
+        assert_eq!(status, StatusCode::OK);
+        assert_eq!(body, "alice");
+        
+        let (status, _, _) = call(app, "/users", Some("af_revoked")).await;
+        assert_eq!(status, StatusCode::UNAUTHORIZED);
+    }
+}
@@ -0,0 +1,178 @@
+use truthlinked_sdk::*;
+use chrono::{TimeZone, Utc};
+use mockito::{Matcher, Server};
+use serde_json::json;
+
+#[cfg(test)]
+mod compliance_report_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_sox_report_default_period() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/sox")
+            .with_status(200)
+            .with_body(json!({
+                "period": "2024-Q1",
+                "total_events": 1200,
+                "audit_trail_complete": true,
+                "no_gaps": true
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let sox = client.get_sox_report().await.unwrap();
+        
+        assert_eq!(sox.period.start, Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap());
+        assert_eq!(sox.period.end, Utc.with_ymd_and_hms(2024, 4, 1, 0, 0, 0).unwrap());
+        assert!(sox.gaps.is_empty());
+        assert!(sox.findings.is_empty());
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_sox_report_for_range_with_findings() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/sox")
+            .match_query(Matcher::Exact(
+                "from=2024-01-01T00%3A00%3A00Z&to=2024-02-01T00%3A00%3A00Z&account=finance".to_string(),
+            ))
+            .with_status(200)
+            .with_body(json!({
+                "period": { "start": "2024-01-01T00:00:00Z", "end": "2024-02-01T00:00:00Z" },
+                "total_events": 40,
+                "audit_trail_complete": false,
+                "no_gaps": false,
+                "gaps": ["2024-01-10T02:00:00Z/2024-01-10T03:30:00Z"],
+                "findings": [{
+                    "control_id": "ITGC-LOG-01",
+                    "status": "fail",
+                    "description": "Audit log ingestion interrupted",
+                    "evidence": [{ "type": "witness_event", "id": "4512" }],
+                    "gaps": [{ "start": "2024-01-10T02:00:00Z", "end": "2024-01-10T03:30:00Z" }]
+                }, {
+                    "control_id": "ITGC-AC-02",
+                    "status": "under_review"
+                }]
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let query = ComplianceQuery::new()
+            .range(
+                Utc.with_ymd_and_hms(2024, 1, 1, 0, 0, 0).unwrap(),
+                Utc.with_ymd_and_hms(2024, 2, 1, 0, 0, 0).unwrap(),
+            )
+            .account("finance");
+        let sox = client.get_sox_report_for(&query).await.unwrap();
+        
+        assert!(!sox.no_gaps);
+        assert_eq!(sox.gaps.len(), 1);
+        assert_eq!(sox.gaps[0].duration(), chrono::Duration::minutes(90));
+        
+        let finding = &sox.findings[0];
+        assert_eq!(finding.status, ControlStatus::Fail);
+        assert_eq!(finding.evidence[0].kind, "witness_event");
+        assert_eq!(finding.gaps, sox.gaps);
+        assert_eq!(sox.findings[1].status, ControlStatus::Unknown);
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_pci_report_for_instance() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/pci")
+            .match_query(Matcher::UrlEncoded("instance".into(), "af-eu-1".into()))
+            .with_status(200)
+            .with_body(json!({
+                "period": "2024-03",
+                "access_controls_enforced": true,
+                "encryption_verified": true,
+                "audit_complete": false,
+                "findings": [{ "control_id": "PCI-10.2.1", "status": "partial" }]
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let pci = client.get_pci_report_for(&ComplianceQuery::new().instance("af-eu-1")).await.unwrap();
+        
+        assert_eq!(pci.period.to_string(), "2024-03-01T00:00:00Z/2024-04-01T00:00:00Z");
+        assert_eq!(pci.findings[0].status, ControlStatus::Partial);
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_hipaa_report_sections() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/hipaa")
+            .match_query(Matcher::UrlEncoded("from".into(), "2024-01-01T00:00:00Z".into()))
+            .with_status(200)
+            .with_body(json!({
+                "period": "2024",
+                "total_events": 310,
+                "phi_access_logged": true,
+                "sections": [{
+                    "id": "164.312(b)",
+                    "title": "Audit controls",
+                    "findings": [{ "control_id": "164.312(b)", "status": "pass" }]
+                }, {
+                    "id": "164.312(d)",
+                    "title": "Person or entity authentication",
+                    "findings": [{ "control_id": "164.312(d)", "status": "fail" }]
+                }]
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let query = ComplianceQuery::new().period("2024".parse().unwrap());
+        let report = client.get_compliance_report(ComplianceFramework::Hipaa, &query).await.unwrap();
+        
+        assert_eq!(report.total_events, Some(310));
+        assert_eq!(report.check("phi_access_logged"), Some(true));
+        assert_eq!(report.section("164.312(b)").unwrap().title.as_deref(), Some("Audit controls"));
+        let failing: Vec<_> = report.all_findings().filter(|f| f.status == ControlStatus::Fail).collect();
+        assert_eq!(failing[0].control_id, "164.312(d)");
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_sox_wrapper_rejects_incomplete_report() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/sox")
+            .with_status(200)
+            .with_body(json!({ "period": "2024", "total_events": 1 }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let result = client.get_sox_report().await;
+        
+        assert!(matches!(result, Err(TruthlinkedError::InvalidResponse)));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_unrecognized_period_rejected() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/compliance/pci")
+            .with_status(200)
+            .with_body(json!({
+                "period": "last month",
+                "access_controls_enforced": true,
+                "encryption_verified": true,
+                "audit_complete": true
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let result = client.get_pci_report().await;
+        
+        assert!(matches!(result, Err(TruthlinkedError::InvalidResponse)));
+        mock.assert_async().await;
+    }
+}
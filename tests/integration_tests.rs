@@ -22,7 +22,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -47,7 +47,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -73,7 +73,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -99,7 +99,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -125,7 +125,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -153,7 +153,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -168,6 +168,20 @@ mod integration_tests {
         mock.assert_async().await;
     }
     
+    #[tokio::test]
+    async fn test_endpoint_invalid_json() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("GET", "/v1/usage")
+            .with_status(200)
+            .with_body("invalid json")
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        assert!(matches!(client.get_usage().await, Err(TruthlinkedError::InvalidResponse)));
+    }
+    
     #[tokio::test]
     async fn test_health_check_with_retry() {
         let mut server = Server::new_async().await;
@@ -194,7 +208,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .retries(2)
             .build()
             .unwrap();
@@ -225,7 +239,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .build()
             .unwrap();
         
@@ -252,7 +266,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .user_agent("CustomApp/2.0")
             .build()
             .unwrap();
@@ -281,7 +295,7 @@ mod integration_tests {
             .create_async()
             .await;
         
-        let client = ClientBuilder::testing(&server.url(), "test_key")
+        let client = ClientBuilder::testing(server.url(), "test_key")
             .header("X-Custom-Header", "custom-value").unwrap()
             .header("X-Request-ID", "12345").unwrap()
             .build()
@@ -292,4 +306,264 @@ mod integration_tests {
         assert!(result.is_ok());
         mock.assert_async().await;
     }
+    
+    #[tokio::test]
+    async fn test_authenticated_endpoint_is_signed() {
+        let mut server = Server::new_async().await;
+        
+        let mock = server.mock("GET", "/v1/shadow/decisions")
+            .match_header("Authorization", "Bearer test_key")
+            .match_header("X-Timestamp", mockito::Matcher::Regex(r"^\d+$".to_string()))
+            .match_header("X-Signature", mockito::Matcher::Regex(r"^[A-Za-z0-9+/]+=*$".to_string()))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!([{
+                "divergence_id": "div_1",
+                "iam_allowed": true,
+                "af_would_allow": false,
+                "breach_prevented": true
+            }]).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .build()
+            .unwrap();
+        
+        let decisions = client.get_shadow_decisions().await.unwrap();
+        
+        assert_eq!(decisions.len(), 1);
+        assert!(decisions[0].breach_prevented);
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_post_endpoint_retries_server_error() {
+        let mut server = Server::new_async().await;
+        
+        let mock_fail = server.mock("POST", "/v1/tokens")
+            .match_header("X-Signature", mockito::Matcher::Any)
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "sso_token": "sso",
+                "requested_scope": ["read:users"]
+            })))
+            .with_status(503)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let mock_success = server.mock("POST", "/v1/tokens")
+            .match_header("X-Signature", mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({
+                "af_token": "af_token_value",
+                "granted_scope": ["read:users"],
+                "expires_at": 1700000000u64,
+                "exchange_id": "ex_1"
+            }).to_string())
+            .expect(1)
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retries(2)
+            .build()
+            .unwrap();
+        
+        let response = client
+            .exchange_token("sso", vec!["read:users".to_string()], [1u8; 32], [2u8; 32])
+            .await
+            .unwrap();
+        
+        assert_eq!(response.exchange_id, "ex_1");
+        mock_fail.assert_async().await;
+        mock_success.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_query_parameters_and_error_body() {
+        let mut server = Server::new_async().await;
+        
+        let mock = server.mock("GET", "/witness/event/7")
+            .match_query(mockito::Matcher::UrlEncoded("include_proof".into(), "true".into()))
+            .with_status(400)
+            .with_body("unknown sequence")
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .build()
+            .unwrap();
+        
+        match client.get_witness_event(7, true).await.unwrap_err() {
+            TruthlinkedError::InvalidRequest(msg) => assert_eq!(msg, "unknown sequence"),
+            e => panic!("Expected InvalidRequest, got {:?}", e),
+        }
+        
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_export_returns_raw_bytes() {
+        let mut server = Server::new_async().await;
+        
+        let mock = server.mock("GET", "/witness/export")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("start_seq".into(), "1".into()),
+                mockito::Matcher::UrlEncoded("end_seq".into(), "5".into()),
+            ]))
+            .match_header("X-Signature", mockito::Matcher::Any)
+            .with_status(200)
+            .with_body(b"raw export")
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .build()
+            .unwrap();
+        
+        let bytes = client.export_witness_chain(Some(1), Some(5)).await.unwrap();
+        
+        assert_eq!(bytes, b"raw export");
+        mock.assert_async().await;
+    }
+}
+
+#[cfg(test)]
+mod authorize_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_authorize_allowed() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/authorize")
+            .match_body(mockito::Matcher::Json(json!({
+                "token": "af_token",
+                "permission": "read:users"
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "authorized": true,
+                "reason": "policy read-users",
+                "decision_id": "dec_1"
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let decision = client.authorize("af_token", "read:users").await.unwrap();
+        
+        assert_eq!(decision, AuthorizationDecision {
+            authorized: true,
+            reason: Some("policy read-users".to_string()),
+            decision_id: Some("dec_1".to_string()),
+        });
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_denied_body() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/v1/authorize")
+            .with_status(200)
+            .with_body(json!({ "authorized": false }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let decision = client.authorize("af_token", "write:users").await.unwrap();
+        
+        assert!(!decision.authorized);
+        assert_eq!(decision.reason, None);
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_403_is_denial() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/v1/authorize")
+            .with_status(403)
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let decision = client.authorize("af_token", "write:users").await.unwrap();
+        
+        assert!(!decision.authorized);
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_401_is_error() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/v1/authorize")
+            .with_status(401)
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        assert!(matches!(
+            client.authorize("af_token", "read:users").await,
+            Err(TruthlinkedError::Unauthorized)
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_many() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/authorize/batch")
+            .match_body(mockito::Matcher::Json(json!({
+                "requests": [
+                    { "token": "af_a", "permission": "read:users" },
+                    { "token": "af_b", "permission": "write:users" }
+                ]
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "decisions": [
+                    { "authorized": true, "decision_id": "dec_1" },
+                    { "authorized": false, "reason": "missing scope", "decision_id": "dec_2" }
+                ]
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let decisions = client
+            .authorize_many([("af_a", "read:users"), ("af_b", "write:users")])
+            .await
+            .unwrap();
+        
+        assert_eq!(decisions.len(), 2);
+        assert!(decisions[0].authorized);
+        assert!(!decisions[1].authorized);
+        assert_eq!(decisions[1].reason.as_deref(), Some("missing scope"));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_many_count_mismatch() {
+        let mut server = Server::new_async().await;
+        let _mock = server.mock("POST", "/v1/authorize/batch")
+            .with_status(200)
+            .with_body(json!({ "decisions": [{ "authorized": true }] }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        assert!(matches!(
+            client.authorize_many([("af_a", "a"), ("af_b", "b")]).await,
+            Err(TruthlinkedError::InvalidResponse)
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_authorize_many_empty() {
+        let server = Server::new_async().await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let pairs: Vec<(String, String)> = Vec::new();
+        assert!(client.authorize_many(pairs).await.unwrap().is_empty());
+    }
 }
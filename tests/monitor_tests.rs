@@ -0,0 +1,290 @@
+use truthlinked_sdk::witness::{self, Hash, MemorySthStore, MerkleAccumulator, MonitorEvent, SthStore, WitnessMonitor};
+use truthlinked_sdk::*;
+use mockito::{Mock, Server, ServerGuard};
+use serde_json::json;
+use std::sync::Arc;
+use std::time::Duration;
+
+fn root_of(leaves: &[Hash]) -> Hash {
+    let mut accumulator = MerkleAccumulator::new();
+    for leaf in leaves {
+        accumulator.push(*leaf);
+    }
+    accumulator.root()
+}
+
+fn leaves(count: usize) -> Vec<Hash> {
+    (0..count).map(|i| witness::leaf_hash(&[i as u8; 32])).collect()
+}
+
+fn head(tree_size: u64, root: Hash, timestamp: u64) -> SignedTreeHead {
+    SignedTreeHead {
+        tree_size,
+        timestamp,
+        root_hash: hex::encode(root),
+        signature: String::new(),
+        key_version: 1,
+    }
+}
+
+fn now() -> u64 {
+    RequestSigner::current_timestamp()
+}
+
+async fn mock_health(server: &mut ServerGuard, chain_size: u64) -> Mock {
+    server.mock("GET", "/witness/health")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(json!({ "status": "healthy", "chainSize": chain_size }).to_string())
+        .create_async()
+        .await
+}
+
+async fn mock_latest(server: &mut ServerGuard, sth: &SignedTreeHead) -> Mock {
+    server.mock("GET", "/witness/sth/latest")
+        .with_status(200)
+        .with_header("content-type", "application/json")
+        .with_body(serde_json::to_string(sth).unwrap())
+        .create_async()
+        .await
+}
+
+fn monitor(server: &ServerGuard, store: MemorySthStore) -> WitnessMonitor {
+    let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+    WitnessMonitor::new(Arc::new(client)).store(store)
+}
+
+fn seeded_store(sth: &SignedTreeHead) -> MemorySthStore {
+    let store = MemorySthStore::new();
+    store.save(sth).unwrap();
+    store
+}
+
+#[cfg(test)]
+mod monitor_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_first_head_trusted_on_first_use() {
+        let mut server = Server::new_async().await;
+        let latest = head(4, root_of(&leaves(4)), now());
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        
+        let events = monitor(&server, MemorySthStore::new()).check().await;
+        
+        assert_eq!(events.len(), 1);
+        assert!(matches!(&events[0], MonitorEvent::HeadAccepted(sth) if sth.tree_size == 4));
+    }
+    
+    #[tokio::test]
+    async fn test_consistent_growth_is_accepted() {
+        let mut server = Server::new_async().await;
+        let all = leaves(8);
+        let trusted = head(4, root_of(&all[..4]), now() - 10);
+        let latest = head(8, root_of(&all), now());
+        let _health = mock_health(&mut server, 8).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        let proof = server.mock("GET", "/witness/consistency")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({
+                "firstSize": 4,
+                "secondSize": 8,
+                "proof": [hex::encode(root_of(&all[4..]))]
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let store = seeded_store(&trusted);
+        let mut monitor = monitor(&server, store);
+        let events = monitor.check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::HeadAccepted(sth)] if sth.tree_size == 8));
+        proof.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_inconsistent_growth_is_rejected() {
+        let mut server = Server::new_async().await;
+        let all = leaves(8);
+        let trusted = head(4, root_of(&all[..4]), now() - 10);
+        let latest = head(8, root_of(&all), now());
+        let _health = mock_health(&mut server, 8).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        let _proof = server.mock("GET", "/witness/consistency")
+            .match_query(mockito::Matcher::Any)
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({
+                "firstSize": 4,
+                "secondSize": 8,
+                "proof": [hex::encode([0u8; 32])]
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let events = monitor(&server, seeded_store(&trusted)).check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::ConsistencyFailure { .. }]));
+    }
+    
+    #[tokio::test]
+    async fn test_rollback_detected() {
+        let mut server = Server::new_async().await;
+        let all = leaves(8);
+        let trusted = head(8, root_of(&all), now());
+        let latest = head(4, root_of(&all[..4]), now());
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        
+        let events = monitor(&server, seeded_store(&trusted)).check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::Rollback { .. }]));
+        assert!(events[0].is_alert());
+    }
+    
+    #[tokio::test]
+    async fn test_split_view_detected() {
+        let mut server = Server::new_async().await;
+        let trusted = head(4, root_of(&leaves(4)), now());
+        let latest = head(4, [9u8; 32], now());
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        
+        let events = monitor(&server, seeded_store(&trusted)).check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::SplitView { .. }]));
+    }
+    
+    #[tokio::test]
+    async fn test_unchanged_head_is_quiet() {
+        let mut server = Server::new_async().await;
+        let trusted = head(4, root_of(&leaves(4)), now());
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = mock_latest(&mut server, &trusted).await;
+        
+        let events = monitor(&server, seeded_store(&trusted)).check().await;
+        
+        assert!(events.is_empty());
+    }
+    
+    #[tokio::test]
+    async fn test_stale_head_detected() {
+        let mut server = Server::new_async().await;
+        let latest = head(4, root_of(&leaves(4)), now() - 3600);
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        
+        let events = monitor(&server, MemorySthStore::new())
+            .max_head_age(Duration::from_secs(60))
+            .check()
+            .await;
+        
+        assert!(matches!(&events[0], MonitorEvent::StaleHead { age, .. } if *age >= Duration::from_secs(3600)));
+        assert!(matches!(&events[1], MonitorEvent::HeadAccepted(_)));
+    }
+    
+    #[tokio::test]
+    async fn test_shrinking_chain_detected() {
+        let mut server = Server::new_async().await;
+        let trusted = head(4, root_of(&leaves(4)), now());
+        let _latest = mock_latest(&mut server, &trusted).await;
+        let mut monitor = monitor(&server, seeded_store(&trusted));
+        
+        let first = mock_health(&mut server, 10).await;
+        assert!(monitor.check().await.is_empty());
+        first.remove_async().await;
+        
+        let _second = mock_health(&mut server, 7).await;
+        let events = monitor.check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::ChainShrunk { previous: 10, current: 7 }]));
+    }
+    
+    #[tokio::test]
+    async fn test_fetch_failure_reported() {
+        let mut server = Server::new_async().await;
+        let _health = mock_health(&mut server, 4).await;
+        let _latest = server.mock("GET", "/witness/sth/latest")
+            .with_status(500)
+            .create_async()
+            .await;
+        
+        let events = monitor(&server, MemorySthStore::new()).check().await;
+        
+        assert!(matches!(&events[..], [MonitorEvent::FetchFailed(TruthlinkedError::ServerError)]));
+    }
+    
+    #[tokio::test]
+    async fn test_gossip_split_view() {
+        let mut server = Server::new_async().await;
+        let all = leaves(8);
+        let trusted = head(8, root_of(&all), now());
+        let ours = head(4, root_of(&all[..4]), now());
+        let _sth = server.mock("GET", "/witness/sth/4")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&ours).unwrap())
+            .create_async()
+            .await;
+        
+        let mut monitor = monitor(&server, seeded_store(&trusted));
+        
+        assert!(monitor.check_gossip(&ours).await.is_empty());
+        
+        let forged = head(4, [3u8; 32], now());
+        let events = monitor.check_gossip(&forged).await;
+        assert!(matches!(&events[..], [MonitorEvent::SplitView { .. }]));
+    }
+    
+    #[tokio::test]
+    async fn test_file_store_round_trip() {
+        let path = std::env::temp_dir().join(format!("truthlinked-sth-{}.json", std::process::id()));
+        let store = witness::FileSthStore::new(&path);
+        
+        assert!(store.load().unwrap().is_none());
+        
+        let sth = head(3, root_of(&leaves(3)), now());
+        store.save(&sth).unwrap();
+        let loaded = store.load().unwrap().unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        assert_eq!(loaded.tree_size, 3);
+        assert_eq!(loaded.root_hash, sth.root_hash);
+    }
+    
+    #[tokio::test]
+    async fn test_file_store_temp_file_distinct() {
+        let dir = std::env::temp_dir().join(format!("truthlinked-sth-dir-{}", std::process::id()));
+        std::fs::create_dir_all(&dir).unwrap();
+        let tmp_store = witness::FileSthStore::new(dir.join("sth.tmp"));
+        let json_store = witness::FileSthStore::new(dir.join("sth.json"));
+        
+        tmp_store.save(&head(5, root_of(&leaves(5)), now())).unwrap();
+        json_store.save(&head(3, root_of(&leaves(3)), now())).unwrap();
+        let tmp_loaded = tmp_store.load().unwrap().unwrap();
+        let json_loaded = json_store.load().unwrap().unwrap();
+        std::fs::remove_dir_all(&dir).unwrap();
+        
+        assert_eq!(tmp_loaded.tree_size, 5);
+        assert_eq!(json_loaded.tree_size, 3);
+    }
+    
+    #[tokio::test]
+    async fn test_spawned_monitor_emits_events() {
+        let mut server = Server::new_async().await;
+        let latest = head(2, root_of(&leaves(2)), now());
+        let _health = mock_health(&mut server, 2).await;
+        let _latest = mock_latest(&mut server, &latest).await;
+        
+        let (task, mut events) = monitor(&server, MemorySthStore::new())
+            .poll_interval(Duration::from_millis(10))
+            .spawn();
+        
+        let event = events.recv().await.unwrap();
+        assert!(matches!(event, MonitorEvent::HeadAccepted(_)));
+        task.abort();
+    }
+}
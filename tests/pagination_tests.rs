@@ -0,0 +1,305 @@
+use truthlinked_sdk::*;
+use futures_util::StreamExt;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::time::Duration;
+
+fn log(timestamp: u64) -> serde_json::Value {
+    json!({
+        "timestamp": timestamp,
+        "event_type": "access",
+        "subject": "alice",
+        "action": "read",
+        "result": "allowed"
+    })
+}
+
+fn page(timestamps: &[u64], next_cursor: Option<&str>) -> String {
+    json!({
+        "logs": timestamps.iter().map(|t| log(*t)).collect::<Vec<_>>(),
+        "next_cursor": next_cursor
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod audit_log_stream_tests {
+    use super::*;
+    
+    async fn two_pages(server: &mut mockito::ServerGuard) -> (mockito::Mock, mockito::Mock) {
+        let first = server.mock("GET", "/v1/audit/logs")
+            .match_query(Matcher::Exact("subject=alice&limit=2".to_string()))
+            .with_status(200)
+            .with_body(page(&[1, 2], Some("c2")))
+            .create_async()
+            .await;
+        let second = server.mock("GET", "/v1/audit/logs")
+            .match_query(Matcher::Exact("subject=alice&limit=2&cursor=c2".to_string()))
+            .with_status(200)
+            .with_body(page(&[3], None))
+            .create_async()
+            .await;
+        (first, second)
+    }
+    
+    fn query() -> AuditLogQuery {
+        AuditLogQuery::new().subject("alice").page_size(2)
+    }
+    
+    #[tokio::test]
+    async fn test_stream_follows_cursors() {
+        let mut server = Server::new_async().await;
+        let (first, second) = two_pages(&mut server).await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let logs: Vec<_> = client.audit_log_stream(query()).collect().await;
+        let timestamps: Vec<u64> = logs.into_iter().map(|log| log.unwrap().timestamp).collect();
+        
+        assert_eq!(timestamps, vec![1, 2, 3]);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_stream_fetches_lazily() {
+        let mut server = Server::new_async().await;
+        let (first, second) = two_pages(&mut server).await;
+        let second = second.expect(0);
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let logs: Vec<_> = client.audit_log_stream(query()).take(2).collect().await;
+        
+        assert_eq!(logs.len(), 2);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limited_page_is_retried() {
+        let mut server = Server::new_async().await;
+        let limited = server.mock("GET", "/v1/audit/logs")
+            .with_status(429)
+            .with_body("slow down")
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server.mock("GET", "/v1/audit/logs")
+            .with_status(200)
+            .with_body(page(&[1], None))
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let logs: Vec<_> = client
+            .audit_log_stream(AuditLogQuery::new())
+            .rate_limit_backoff(Duration::from_millis(10))
+            .collect()
+            .await;
+        
+        assert_eq!(logs.len(), 1);
+        assert!(logs[0].is_ok());
+        limited.assert_async().await;
+        ok.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limit_retries_exhausted() {
+        let mut server = Server::new_async().await;
+        let limited = server.mock("GET", "/v1/audit/logs")
+            .with_status(429)
+            .expect(2)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let mut stream = client
+            .audit_log_stream(AuditLogQuery::new())
+            .rate_limit_backoff(Duration::from_millis(1))
+            .rate_limit_retries(1);
+        
+        assert!(matches!(stream.next().await, Some(Err(TruthlinkedError::RateLimitExceeded(_)))));
+        assert!(stream.next().await.is_none());
+        limited.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_rate_limits_left_to_client_retries() {
+        let mut server = Server::new_async().await;
+        let limited = server.mock("GET", "/v1/audit/logs")
+            .with_status(429)
+            .with_header("retry-after", "0")
+            .expect(2)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retry_config(RetryConfig {
+                max_attempts: 2,
+                initial_delay: Duration::from_millis(1),
+                max_delay: Duration::from_millis(10),
+                backoff_multiplier: 2.0,
+                jitter_factor: 0.0,
+            })
+            .honor_retry_after(true)
+            .build()
+            .unwrap();
+        
+        // Only the client's attempts reach the server, not one round per stream retry
+        let mut stream = client
+            .audit_log_stream(AuditLogQuery::new())
+            .rate_limit_backoff(Duration::from_millis(1));
+        
+        assert!(matches!(stream.next().await, Some(Err(TruthlinkedError::RateLimitExceeded(_)))));
+        limited.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_resume_from_checkpoint() {
+        let mut server = Server::new_async().await;
+        let _pages = two_pages(&mut server).await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let mut stream = client.audit_log_stream(query());
+        assert_eq!(stream.checkpoint(), StreamCheckpoint::default());
+        assert_eq!(stream.next().await.unwrap().unwrap().timestamp, 1);
+        
+        let checkpoint = stream.checkpoint();
+        assert_eq!(checkpoint, StreamCheckpoint { cursor: None, offset: 1 });
+        
+        // Survives a restart
+        let saved = serde_json::to_string(&checkpoint).unwrap();
+        let restored: StreamCheckpoint = serde_json::from_str(&saved).unwrap();
+        
+        let mut resumed = client.audit_log_stream(query()).resume_from(restored);
+        assert_eq!(resumed.next().await.unwrap().unwrap().timestamp, 2);
+        assert_eq!(resumed.checkpoint(), StreamCheckpoint { cursor: Some("c2".to_string()), offset: 0 });
+        
+        let rest: Vec<_> = resumed.collect().await;
+        assert_eq!(rest.len(), 1);
+        assert_eq!(rest[0].as_ref().unwrap().timestamp, 3);
+    }
+}
+
+fn decision(id: &str) -> serde_json::Value {
+    json!({
+        "divergence_id": id,
+        "iam_allowed": true,
+        "af_would_allow": false,
+        "breach_prevented": true,
+        "timestamp": 1_700_000_000
+    })
+}
+
+fn decision_page(ids: &[&str], next_cursor: Option<&str>) -> String {
+    json!({
+        "decisions": ids.iter().map(|id| decision(id)).collect::<Vec<_>>(),
+        "next_cursor": next_cursor
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod shadow_decision_stream_tests {
+    use super::*;
+    
+    async fn ids(stream: PageStream<'_, ShadowDecision>, count: usize) -> Vec<String> {
+        stream
+            .take(count)
+            .map(|decision| decision.unwrap().divergence_id)
+            .collect()
+            .await
+    }
+    
+    #[tokio::test]
+    async fn test_filters_and_ends_when_caught_up() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("breach_prevented=true&direction=iam_allowed_af_denied".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d1"], None))
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let query = ShadowDecisionQuery::new()
+            .breach_prevented(true)
+            .direction(DivergenceDirection::IamAllowedAfDenied);
+        let decisions: Vec<_> = client.shadow_decision_stream(query).collect().await;
+        
+        assert_eq!(decisions.len(), 1);
+        let decision = decisions[0].as_ref().unwrap();
+        assert_eq!(decision.divergence(), Some(DivergenceDirection::IamAllowedAfDenied));
+        assert_eq!(decision.timestamp, Some(1_700_000_000));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_follow_waits_for_new_decisions() {
+        let mut server = Server::new_async().await;
+        let _first = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("wait=20".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d1"], Some("c1")))
+            .create_async()
+            .await;
+        let idle = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("cursor=c1&wait=20".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&[], None))
+            .expect(1)
+            .create_async()
+            .await;
+        let _new = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("cursor=c1&wait=20".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d2"], Some("c2")))
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let mut feed = client
+            .follow_shadow_decisions(ShadowDecisionQuery::new())
+            .idle_delay(Duration::from_millis(10));
+        
+        assert_eq!(feed.next().await.unwrap().unwrap().divergence_id, "d1");
+        assert_eq!(feed.next().await.unwrap().unwrap().divergence_id, "d2");
+        assert_eq!(feed.checkpoint(), StreamCheckpoint { cursor: Some("c2".to_string()), offset: 0 });
+        idle.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_follow_repolls_tail_without_repeats() {
+        let mut server = Server::new_async().await;
+        let first = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("wait=5".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d1"], None))
+            .expect(1)
+            .create_async()
+            .await;
+        // Each re-poll tells the server how much of the page was received
+        let grown = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("offset=1&wait=5".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d1", "d2", "d3"], None))
+            .expect(1)
+            .create_async()
+            .await;
+        let grown_again = server.mock("GET", "/v1/shadow/decisions")
+            .match_query(Matcher::Exact("offset=3&wait=5".to_string()))
+            .with_status(200)
+            .with_body(decision_page(&["d1", "d2", "d3", "d4"], None))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let feed = client
+            .follow_shadow_decisions(ShadowDecisionQuery::new().wait(Duration::from_secs(5)))
+            .idle_delay(Duration::from_millis(10));
+        
+        assert_eq!(ids(feed, 4).await, vec!["d1", "d2", "d3", "d4"]);
+        first.assert_async().await;
+        grown.assert_async().await;
+        grown_again.assert_async().await;
+    }
+}
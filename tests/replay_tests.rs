@@ -0,0 +1,220 @@
+use truthlinked_sdk::*;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+fn counters(events: u64, breaches: u64) -> String {
+    json!({
+        "events_processed": events,
+        "breaches_prevented": breaches,
+        "false_positives_avoided": 0
+    })
+    .to_string()
+}
+
+#[cfg(test)]
+mod chunked_replay_tests {
+    use super::*;
+    
+    async fn chunk_mock(server: &mut mockito::ServerGuard, logs: &[&str], status: usize) -> mockito::Mock {
+        server.mock("POST", "/v1/shadow/replay")
+            .match_body(Matcher::Json(json!({ "logs": logs, "adapter": "okta" })))
+            .with_status(status)
+            .with_body(counters(logs.len() as u64, 1))
+            .create_async()
+            .await
+    }
+    
+    async fn keyed_chunk_mock(server: &mut mockito::ServerGuard, logs: &[&str], status: usize, key: &str) -> mockito::Mock {
+        server.mock("POST", "/v1/shadow/replay")
+            .match_header("Idempotency-Key", key)
+            .match_body(Matcher::Json(json!({ "logs": logs, "adapter": "okta" })))
+            .with_status(status)
+            .with_body(counters(logs.len() as u64, 1))
+            .create_async()
+            .await
+    }
+    
+    #[tokio::test]
+    async fn test_lines_uploaded_in_chunks() {
+        let mut server = Server::new_async().await;
+        let first = chunk_mock(&mut server, &["a", "b"], 200).await;
+        let second = chunk_mock(&mut server, &["c", "d"], 200).await;
+        let third = chunk_mock(&mut server, &["e"], 200).await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let reported = Arc::new(Mutex::new(Vec::new()));
+        let sink = reported.clone();
+        let upload = ReplayUpload::new("okta")
+            .chunk_lines(2)
+            .on_progress(move |progress| sink.lock().unwrap().push(progress.lines_acknowledged));
+        
+        let result = client.replay_iam_log_lines(["a", "b", "c", "d", "e"], upload).await.unwrap();
+        
+        assert_eq!(result.chunks_acknowledged, 3);
+        assert_eq!(result.lines_acknowledged, 5);
+        assert_eq!(result.totals.events_processed, 5);
+        assert_eq!(result.totals.breaches_prevented, 3);
+        assert_eq!(*reported.lock().unwrap(), vec![2, 4, 5]);
+        first.assert_async().await;
+        second.assert_async().await;
+        third.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_reader_uploaded_in_chunks() {
+        let mut server = Server::new_async().await;
+        let first = chunk_mock(&mut server, &["a", "b"], 200).await;
+        let second = chunk_mock(&mut server, &["c"], 200).await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let input: &[u8] = b"a\r\nb\n\nc\n";
+        let result = client
+            .replay_iam_log_reader(input, ReplayUpload::new("okta").chunk_lines(2))
+            .await
+            .unwrap();
+        
+        assert_eq!(result.chunks_acknowledged, 2);
+        assert_eq!(result.lines_acknowledged, 4);
+        first.assert_async().await;
+        second.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_resume_after_failed_chunk() {
+        let mut server = Server::new_async().await;
+        let first = chunk_mock(&mut server, &["a", "b"], 200).await;
+        let rejected = chunk_mock(&mut server, &["c", "d"], 400).await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let saved = Arc::new(Mutex::new(ReplayProgress::default()));
+        let sink = saved.clone();
+        let upload = ReplayUpload::new("okta")
+            .chunk_lines(2)
+            .on_progress(move |progress| *sink.lock().unwrap() = progress.clone());
+        
+        let lines = ["a", "b", "c", "d", "e"];
+        let result = client.replay_iam_log_lines(lines, upload).await;
+        assert!(matches!(result, Err(TruthlinkedError::InvalidRequest(_))));
+        first.assert_async().await;
+        rejected.assert_async().await;
+        
+        // Only the unacknowledged chunks are sent again
+        server.reset();
+        let first = chunk_mock(&mut server, &["a", "b"], 200).await.expect(0);
+        let second = chunk_mock(&mut server, &["c", "d"], 200).await;
+        let third = chunk_mock(&mut server, &["e"], 200).await;
+        
+        let progress = saved.lock().unwrap().clone();
+        let upload = ReplayUpload::new("okta").chunk_lines(2).resume_from(progress);
+        let result = client.replay_iam_log_lines(lines, upload).await.unwrap();
+        
+        assert_eq!(result.chunks_acknowledged, 3);
+        assert_eq!(result.totals.events_processed, 5);
+        first.assert_async().await;
+        second.assert_async().await;
+        third.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_chunk_keys_stable_across_resume() {
+        let mut server = Server::new_async().await;
+        let key = IdempotencyKey::new("replay-2024-01").unwrap();
+        let first = keyed_chunk_mock(&mut server, &["a", "b"], 200, "replay-2024-01-2-4194304-0").await;
+        let lost = keyed_chunk_mock(&mut server, &["c", "d"], 503, "replay-2024-01-2-4194304-2").await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let saved = Arc::new(Mutex::new(ReplayProgress::default()));
+        let sink = saved.clone();
+        let upload = ReplayUpload::new("okta")
+            .chunk_lines(2)
+            .idempotency_key(key.clone())
+            .on_progress(move |progress| *sink.lock().unwrap() = progress.clone());
+        
+        let lines = ["a", "b", "c", "d", "e"];
+        assert!(client.replay_iam_log_lines(lines, upload).await.is_err());
+        first.assert_async().await;
+        lost.assert_async().await;
+        
+        // The resent chunk carries the key the server may already have seen
+        server.reset();
+        let second = keyed_chunk_mock(&mut server, &["c", "d"], 200, "replay-2024-01-2-4194304-2").await;
+        let third = keyed_chunk_mock(&mut server, &["e"], 200, "replay-2024-01-2-4194304-4").await;
+        
+        let progress = saved.lock().unwrap().clone();
+        let upload = ReplayUpload::new("okta").chunk_lines(2).idempotency_key(key).resume_from(progress);
+        client.replay_iam_log_lines(lines, upload).await.unwrap();
+        second.assert_async().await;
+        third.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_typed_adapter_rejects_locally() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/shadow/replay")
+            .match_body(Matcher::Json(json!({
+                "logs": [r#"{"actor":{"id":"u1"},"eventType":"e","published":"p","uuid":"1"}"#],
+                "adapter": "okta"
+            })))
+            .with_status(200)
+            .with_body(counters(1, 0))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let rejected = Arc::new(Mutex::new(Vec::new()));
+        let sink = rejected.clone();
+        let upload = ReplayUpload::for_adapter(IamAdapter::Okta)
+            .on_malformed(move |malformed| sink.lock().unwrap().push(malformed.clone()));
+        
+        let lines = [r#"{"actor": {"id": "u1"}, "eventType": "e", "published": "p", "uuid": "1"}"#, r#"{"actor": {}, "eventType": "e", "published": "p"}"#];
+        let result = client.replay_iam_log_lines(lines, upload).await.unwrap();
+        
+        assert_eq!(result.lines_acknowledged, 2);
+        assert_eq!(result.lines_rejected, 1);
+        assert_eq!(
+            *rejected.lock().unwrap(),
+            vec![MalformedLine { line: 2, reason: "missing \"uuid\"".to_string() }]
+        );
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_all_lines_rejected_uploads_nothing() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/shadow/replay")
+            .expect(0)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let upload = ReplayUpload::for_adapter(IamAdapter::AwsCloudTrail);
+        let result = client.replay_iam_log_lines(["{}", "not json"], upload).await.unwrap();
+        
+        assert_eq!(result.chunks_acknowledged, 0);
+        assert_eq!(result.lines_rejected, 2);
+        mock.assert_async().await;
+    }
+    
+    #[cfg(feature = "gzip")]
+    #[tokio::test]
+    async fn test_gzip_chunks() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("POST", "/v1/shadow/replay")
+            .match_header("Content-Encoding", "gzip")
+            .match_header("Content-Type", "application/json")
+            .with_status(200)
+            .with_body(counters(2, 0))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let upload = ReplayUpload::new("okta").gzip(true);
+        let result = client.replay_iam_log_lines(["a", "b"], upload).await.unwrap();
+        
+        assert_eq!(result.totals.events_processed, 2);
+        mock.assert_async().await;
+    }
+}
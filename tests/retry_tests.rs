@@ -1,7 +1,19 @@
 use truthlinked_sdk::*;
 use std::sync::atomic::{AtomicU32, Ordering};
-use std::sync::Arc;
+use std::sync::{Arc, Mutex};
 use std::time::Duration;
+use mockito::Server;
+
+fn submission() -> WitnessSubmission {
+    WitnessSubmission {
+        af_event_hash: "00".repeat(32),
+        af_merkle_root: "00".repeat(32),
+        af_sequence: 0,
+        af_instance_id: "af-1".to_string(),
+        oracle_time: 1_700_000_000,
+        af_signature: String::new(),
+    }
+}
 
 #[cfg(test)]
 mod retry_tests {
@@ -158,7 +170,7 @@ mod retry_tests {
         let result: Result<&str> = executor.execute(|| {
             attempt_count_clone.fetch_add(1, Ordering::SeqCst);
             async move {
-                Err::<&str, _>(TruthlinkedError::RateLimitExceeded("Too many requests".to_string()))
+                Err::<&str, _>(TruthlinkedError::RateLimitExceeded("Too many requests".into()))
             }
         }).await;
         
@@ -197,3 +209,229 @@ mod retry_tests {
         // This is tested indirectly through the retry behavior
     }
 }
+
+#[cfg(test)]
+mod retry_after_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_rate_limit_headers_surfaced() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/health")
+            .with_status(429)
+            .with_header("Retry-After", "30")
+            .with_header("X-RateLimit-Remaining", "0")
+            .with_header("X-RateLimit-Reset", "45")
+            .with_body("Monthly quota exhausted")
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").retries(3).build().unwrap();
+        
+        // Not retried unless opted in
+        let error = client.health().await.unwrap_err();
+        
+        let TruthlinkedError::RateLimitExceeded(info) = &error else {
+            panic!("Expected RateLimitExceeded, got {:?}", error);
+        };
+        assert_eq!(info.message, "Monthly quota exhausted");
+        assert_eq!(info.remaining, Some(0));
+        assert_eq!(info.reset_after, Some(Duration::from_secs(45)));
+        assert_eq!(error.retry_after(), Some(Duration::from_secs(30)));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_honor_retry_after() {
+        let mut server = Server::new_async().await;
+        let limited = server.mock("GET", "/health")
+            .with_status(429)
+            .with_header("Retry-After", "0")
+            .expect(1)
+            .create_async()
+            .await;
+        let ok = server.mock("GET", "/health")
+            .with_status(200)
+            .with_body(r#"{"status":"ok","version":"1.0.0"}"#)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retries(2)
+            .honor_retry_after(true)
+            .build()
+            .unwrap();
+        
+        assert_eq!(client.health().await.unwrap().status, "ok");
+        limited.assert_async().await;
+        ok.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_service_unavailable_with_retry_after() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/health")
+            .with_status(503)
+            .with_header("Retry-After", "0")
+            .expect(2)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").retries(2).build().unwrap();
+        
+        let error = client.health().await.unwrap_err();
+        
+        assert!(matches!(error, TruthlinkedError::ServiceUnavailable(_)));
+        assert_eq!(error.retry_after(), Some(Duration::ZERO));
+        mock.assert_async().await;
+    }
+}
+
+#[cfg(test)]
+mod retry_policy_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_custom_policy_replaces_retry_config() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/health")
+            .with_status(500)
+            .expect(3)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retry_policy(FixedDelay::new(Duration::from_millis(1), 3))
+            .build()
+            .unwrap();
+        
+        assert!(matches!(client.health().await, Err(TruthlinkedError::ServerError)));
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_policy_wins_over_retries() {
+        let mut server = Server::new_async().await;
+        let mock = server.mock("GET", "/health")
+            .with_status(500)
+            .expect(2)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retry_policy(FixedDelay::new(Duration::from_millis(1), 2))
+            .retries(5)
+            .build()
+            .unwrap();
+        
+        assert!(client.health().await.is_err());
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_endpoint_policy_overrides_default() {
+        let mut server = Server::new_async().await;
+        let health = server.mock("GET", "/health")
+            .with_status(500)
+            .expect(2)
+            .create_async()
+            .await;
+        let submit = server.mock("POST", "/witness/submit")
+            .with_status(500)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retry_policy(FixedDelay::new(Duration::from_millis(1), 2))
+            .endpoint_retry_policy("/witness/submit", NoRetry)
+            .build()
+            .unwrap();
+        
+        assert!(client.health().await.is_err());
+        assert!(client.submit_witness(submission()).await.is_err());
+        health.assert_async().await;
+        submit.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_deadline_stops_retrying() {
+        let executor = RetryExecutor::with_policy(
+            FixedDelay::new(Duration::from_millis(30), 10).with_deadline(Duration::from_millis(75)),
+        );
+        let attempt_count = Arc::new(AtomicU32::new(0));
+        let attempt_count_clone = attempt_count.clone();
+        
+        let result: Result<&str> = executor.execute(|| {
+            attempt_count_clone.fetch_add(1, Ordering::SeqCst);
+            async move { Err(TruthlinkedError::ServerError) }
+        }).await;
+        
+        assert!(result.is_err());
+        assert_eq!(attempt_count.load(Ordering::SeqCst), 3);
+    }
+}
+
+#[cfg(test)]
+mod idempotency_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_generated_key_reused_across_retries() {
+        let mut server = Server::new_async().await;
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let record = |keys: Arc<Mutex<Vec<String>>>| {
+            move |request: &mockito::Request| {
+                let key = request.header("Idempotency-Key")[0].to_str().unwrap().to_string();
+                keys.lock().unwrap().push(key);
+                Vec::new()
+            }
+        };
+        let failure = server.mock("POST", "/v1/tokens")
+            .with_status(503)
+            .with_body_from_request(record(keys.clone()))
+            .expect(1)
+            .create_async()
+            .await;
+        let success = server.mock("POST", "/v1/tokens")
+            .with_status(200)
+            .with_body_from_request(record(keys.clone()))
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .retry_policy(FixedDelay::new(Duration::from_millis(1), 2))
+            .build()
+            .unwrap();
+        
+        // The empty success body fails to parse; only the keys matter here
+        let _ = client.exchange_token("sso", vec![], [0u8; 32], [0u8; 32]).await;
+        failure.assert_async().await;
+        success.assert_async().await;
+        
+        let keys = keys.lock().unwrap();
+        assert_eq!(keys.len(), 2);
+        assert_eq!(keys[0], keys[1]);
+        assert!(IdempotencyKey::new(keys[0].as_str()).is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_caller_supplied_witness_key() {
+        let mut server = Server::new_async().await;
+        let key = IdempotencyKey::for_witness(&submission());
+        let mock = server.mock("POST", "/witness/submit")
+            .match_header("Idempotency-Key", key.as_str())
+            .with_status(200)
+            .with_body(serde_json::json!({
+                "sequence": 0,
+                "timestamp": 1_700_000_000u64,
+                "submission": submission(),
+                "prevHash": "00".repeat(32),
+                "eventHash": "11".repeat(32),
+                "proof": null
+            }).to_string())
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let event = client.submit_witness_with_key(submission(), key).await.unwrap();
+        assert_eq!(event.sequence, 0);
+        mock.assert_async().await;
+    }
+}
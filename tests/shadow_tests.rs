@@ -0,0 +1,115 @@
+use truthlinked_sdk::*;
+use mockito::{Matcher, Server};
+use serde_json::json;
+use std::sync::{Arc, Mutex};
+
+#[cfg(test)]
+mod shadow_evaluator_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_evaluate_with_fetched_policy_and_flush() {
+        let mut server = Server::new_async().await;
+        let _policy = server.mock("GET", "/v1/shadow/policy")
+            .with_status(200)
+            .with_body(json!({
+                "version": "7",
+                "rules": [{ "principal": "*", "action": "read", "resource": "docs/*" }]
+            }).to_string())
+            .create_async()
+            .await;
+        let upload = server.mock("POST", "/v1/shadow/decisions")
+            .match_body(Matcher::PartialJson(json!({
+                "decisions": [{
+                    "iam_allowed": true,
+                    "af_would_allow": false,
+                    "breach_prevented": true,
+                    "principal": "bob",
+                    "action": "delete",
+                    "resource": "docs/1"
+                }]
+            })))
+            .with_status(202)
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let policy = client.get_shadow_policy().await.unwrap();
+        assert_eq!(policy.version.as_deref(), Some("7"));
+        assert_eq!(policy.rules[0].effect, PolicyEffect::Allow);
+        
+        let evaluator = ShadowEvaluator::new(policy);
+        assert!(evaluator.evaluate(&IamDecision::new("bob", "read", "docs/1", true)).af_would_allow);
+        assert!(evaluator.evaluate(&IamDecision::new("bob", "delete", "docs/1", true)).breach_prevented);
+        
+        assert_eq!(evaluator.flush(&client).await.unwrap(), 1);
+        assert_eq!(evaluator.pending(), 0);
+        assert_eq!(evaluator.flush(&client).await.unwrap(), 0);
+        upload.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_failed_flush_keeps_decisions() {
+        let mut server = Server::new_async().await;
+        let _rejected = server.mock("POST", "/v1/shadow/decisions")
+            .with_status(403)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let evaluator = ShadowEvaluator::new(ShadowPolicy::default());
+        let first = evaluator.evaluate(&IamDecision::new("bob", "read", "docs/1", true));
+        evaluator.evaluate(&IamDecision::new("bob", "read", "docs/2", true));
+        
+        assert!(matches!(evaluator.flush(&client).await, Err(TruthlinkedError::Forbidden)));
+        
+        let pending = evaluator.drain();
+        assert_eq!(pending.len(), 2);
+        assert_eq!(pending[0].divergence_id, first.divergence_id);
+    }
+    
+    #[tokio::test]
+    async fn test_failed_batch_resent_with_same_key() {
+        let mut server = Server::new_async().await;
+        let keys = Arc::new(Mutex::new(Vec::new()));
+        let sink = keys.clone();
+        let lost = server.mock("POST", "/v1/shadow/decisions")
+            .with_status(500)
+            .with_body_from_request(move |request| {
+                let key = request.header("Idempotency-Key")[0].to_str().unwrap().to_string();
+                sink.lock().unwrap().push(key);
+                Vec::new()
+            })
+            .expect(1)
+            .create_async()
+            .await;
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        
+        let evaluator = ShadowEvaluator::new(ShadowPolicy::default());
+        evaluator.evaluate(&IamDecision::new("bob", "read", "docs/1", true));
+        evaluator.evaluate(&IamDecision::new("bob", "read", "docs/2", true));
+        assert!(evaluator.flush(&client).await.is_err());
+        lost.assert_async().await;
+        
+        // Divergences seen after the failure wait for the next batch
+        evaluator.evaluate(&IamDecision::new("bob", "read", "docs/3", true));
+        assert_eq!(evaluator.pending(), 3);
+        
+        server.reset();
+        let key = keys.lock().unwrap()[0].clone();
+        let resent = server.mock("POST", "/v1/shadow/decisions")
+            .match_header("Idempotency-Key", key.as_str())
+            .match_body(Matcher::PartialJson(json!({
+                "decisions": [{ "resource": "docs/1" }, { "resource": "docs/2" }]
+            })))
+            .with_status(202)
+            .expect(1)
+            .create_async()
+            .await;
+        
+        assert_eq!(evaluator.flush(&client).await.unwrap(), 2);
+        resent.assert_async().await;
+        assert_eq!(evaluator.pending(), 1);
+    }
+}
@@ -0,0 +1,175 @@
+use truthlinked_sdk::*;
+use base64::{engine::general_purpose, Engine as _};
+use rcgen::{BasicConstraints, Certificate as RcgenCert, CertificateParams, IsCa};
+use rustls::server::AllowAnyAuthenticatedClient;
+use rustls::{Certificate, PrivateKey, RootCertStore, ServerConfig};
+use sha2::{Digest, Sha256};
+use std::sync::Arc;
+use tokio::io::{AsyncReadExt, AsyncWriteExt};
+use tokio::net::TcpListener;
+
+/// A private CA with a `localhost` server certificate and a client certificate
+struct Pki {
+    ca: RcgenCert,
+    server: RcgenCert,
+    client: RcgenCert,
+}
+
+impl Pki {
+    fn new() -> Self {
+        let mut ca_params = CertificateParams::new(vec![]);
+        ca_params.is_ca = IsCa::Ca(BasicConstraints::Unconstrained);
+        
+        Self {
+            ca: RcgenCert::from_params(ca_params).unwrap(),
+            server: RcgenCert::from_params(CertificateParams::new(vec!["localhost".to_string()])).unwrap(),
+            client: RcgenCert::from_params(CertificateParams::new(vec!["client".to_string()])).unwrap(),
+        }
+    }
+    
+    fn ca_pem(&self) -> String {
+        self.ca.serialize_pem().unwrap()
+    }
+    
+    fn client_pem(&self) -> String {
+        self.client.serialize_pem_with_signer(&self.ca).unwrap()
+    }
+    
+    fn server_pin(&self) -> String {
+        general_purpose::STANDARD.encode(Sha256::digest(self.server.get_key_pair().public_key_der()))
+    }
+    
+    /// Serve `/health` over TLS on localhost, optionally requiring a client certificate
+    async fn serve(&self, require_client_cert: bool) -> String {
+        let builder = ServerConfig::builder().with_safe_defaults();
+        let builder = if require_client_cert {
+            let mut roots = RootCertStore::empty();
+            roots.add(&Certificate(self.ca.serialize_der().unwrap())).unwrap();
+            builder.with_client_cert_verifier(AllowAnyAuthenticatedClient::new(roots).boxed())
+        } else {
+            builder.with_no_client_auth()
+        };
+        let config = builder
+            .with_single_cert(
+                vec![Certificate(self.server.serialize_der_with_signer(&self.ca).unwrap())],
+                PrivateKey(self.server.serialize_private_key_der()),
+            )
+            .unwrap();
+        
+        let acceptor = tokio_rustls::TlsAcceptor::from(Arc::new(config));
+        let listener = TcpListener::bind("127.0.0.1:0").await.unwrap();
+        let port = listener.local_addr().unwrap().port();
+        
+        tokio::spawn(async move {
+            while let Ok((stream, _)) = listener.accept().await {
+                let acceptor = acceptor.clone();
+                tokio::spawn(async move {
+                    let Ok(mut tls) = acceptor.accept(stream).await else { return };
+                    let mut request = [0u8; 1024];
+                    let _ = tls.read(&mut request).await;
+                    let body = r#"{"status":"ok","version":"1.0.0"}"#;
+                    let response = format!(
+                        "HTTP/1.1 200 OK\r\ncontent-type: application/json\r\ncontent-length: {}\r\nconnection: close\r\n\r\n{}",
+                        body.len(),
+                        body
+                    );
+                    let _ = tls.write_all(response.as_bytes()).await;
+                    let _ = tls.shutdown().await;
+                });
+            }
+        });
+        format!("https://localhost:{}", port)
+    }
+}
+
+fn builder(url: &str) -> ClientBuilder {
+    ClientBuilder::new(url, "test_key")
+        .retry_config(RetryConfig::none())
+        .disable_logging()
+}
+
+#[cfg(test)]
+mod private_ca_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_private_ca_trusted() {
+        let pki = Pki::new();
+        let url = pki.serve(false).await;
+        
+        let client = builder(&url)
+            .add_root_certificate(pki.ca_pem())
+            .unwrap()
+            .tls_built_in_roots(false)
+            .build()
+            .unwrap();
+        
+        assert_eq!(client.health().await.unwrap().status, "ok");
+    }
+    
+    #[tokio::test]
+    async fn test_private_ca_untrusted_by_default() {
+        let pki = Pki::new();
+        let url = pki.serve(false).await;
+        
+        let client = builder(&url).build().unwrap();
+        
+        assert!(matches!(client.health().await, Err(TruthlinkedError::Network(_))));
+    }
+    
+    #[tokio::test]
+    async fn test_mutual_tls() {
+        let pki = Pki::new();
+        let url = pki.serve(true).await;
+        
+        let client = builder(&url)
+            .add_root_certificate(pki.ca_pem())
+            .unwrap()
+            .client_identity(pki.client_pem(), pki.client.serialize_private_key_pem())
+            .unwrap()
+            .build()
+            .unwrap();
+        assert_eq!(client.health().await.unwrap().version, "1.0.0");
+        
+        let anonymous = builder(&url).add_root_certificate(pki.ca_pem()).unwrap().build().unwrap();
+        assert!(matches!(anonymous.health().await, Err(TruthlinkedError::Network(_))));
+    }
+}
+
+#[cfg(test)]
+mod certificate_pin_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_pin_match_with_backup() {
+        let pki = Pki::new();
+        let url = pki.serve(false).await;
+        let stale = general_purpose::STANDARD.encode([0u8; 32]);
+        
+        let client = builder(&url)
+            .add_root_certificate(pki.ca_pem())
+            .unwrap()
+            .certificate_pin(stale)
+            .certificate_pin(format!("sha256/{}", pki.server_pin()))
+            .build()
+            .unwrap();
+        
+        assert!(client.health().await.is_ok());
+    }
+    
+    #[tokio::test]
+    async fn test_pin_mismatch() {
+        let pki = Pki::new();
+        let url = pki.serve(false).await;
+        let other = Pki::new();
+        
+        let client = builder(&url)
+            .add_root_certificate(pki.ca_pem())
+            .unwrap()
+            .certificate_pin(other.server_pin())
+            .build()
+            .unwrap();
+        
+        assert!(matches!(client.health().await, Err(TruthlinkedError::CertificatePinMismatch)));
+    }
+}
@@ -0,0 +1,544 @@
+use truthlinked_sdk::*;
+use base64::Engine;
+use ed25519_dalek::{Signer, SigningKey};
+use mockito::{Mock, Server, ServerGuard};
+use serde_json::{json, Value};
+use std::sync::Arc;
+
+fn signing_key(seed: u8) -> SigningKey {
+    SigningKey::from_bytes(&[seed; 32])
+}
+
+fn now() -> u64 {
+    RequestSigner::current_timestamp()
+}
+
+fn claims(scope: &[&str], exp: u64) -> Value {
+    json!({
+        "sub": "alice",
+        "scope": scope,
+        "exp": exp,
+        "jti": "tok_1",
+        "cb": hex::encode([7u8; 32]),
+    })
+}
+
+fn mint(key: &SigningKey, kid: u32, claims: &Value) -> String {
+    let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+    let header = engine.encode(json!({"alg": "EdDSA", "kid": kid}).to_string());
+    let claims = engine.encode(claims.to_string());
+    let signing_input = format!("{}.{}", header, claims);
+    let signature = engine.encode(key.sign(signing_input.as_bytes()).to_bytes());
+    format!("{}.{}", signing_input, signature)
+}
+
+fn key_set(keys: &[(u32, &SigningKey)]) -> String {
+    let keys: Vec<Value> = keys
+        .iter()
+        .map(|(version, key)| json!({
+            "key_version": version,
+            "public_key": base64::engine::general_purpose::STANDARD.encode(key.verifying_key().as_bytes()),
+        }))
+        .collect();
+    json!({ "keys": keys }).to_string()
+}
+
+async fn mock_keys(server: &mut ServerGuard, keys: &[(u32, &SigningKey)], hits: usize) -> Mock {
+    server.mock("GET", "/v1/tokens/keys")
+        .with_status(200)
+        .with_body(key_set(keys))
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+async fn mock_validate(server: &mut ServerGuard, body: Value, hits: usize) -> Mock {
+    server.mock("GET", "/v1/tokens/tok_1/validate")
+        .with_status(200)
+        .with_body(body.to_string())
+        .expect(hits)
+        .create_async()
+        .await
+}
+
+fn verifier(server: &ServerGuard) -> TokenVerifier {
+    let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+    TokenVerifier::new(Arc::new(client))
+}
+
+#[cfg(test)]
+mod verifier_tests {
+    use super::*;
+    
+    #[tokio::test]
+    async fn test_valid_token_verified_locally() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        let validate = mock_validate(&mut server, json!({"valid": true}), 0).await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&key, 1, &claims(&["read:users"], now() + 300));
+        let requirements = TokenRequirements::new().scope("read:users");
+        
+        for _ in 0..3 {
+            let result = verifier.verify(&token, &requirements).await.unwrap();
+            assert!(result.valid);
+            assert_eq!(result.subject.as_deref(), Some("alice"));
+            assert_eq!(result.scope, Some(vec!["read:users".to_string()]));
+        }
+        
+        // Keys fetched once, never a validate round trip
+        keys.assert_async().await;
+        validate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_expired_token_rejected() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let keys = mock_keys(&mut server, &[(1, &key)], 0).await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&key, 1, &claims(&[], now() - 120));
+        
+        let result = verifier.verify(&token, &TokenRequirements::new()).await.unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.subject, None);
+        keys.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_expiry_leeway() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let _keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        
+        let verifier = verifier(&server).leeway(std::time::Duration::from_secs(600));
+        let token = mint(&key, 1, &claims(&[], now() - 120));
+        
+        assert!(verifier.verify(&token, &TokenRequirements::new()).await.unwrap().valid);
+    }
+    
+    #[tokio::test]
+    async fn test_tampered_claims_rejected() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let _keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&key, 1, &claims(&["read:users"], now() + 300));
+        let forged = mint(&signing_key(9), 1, &claims(&["admin"], now() + 300));
+        
+        // Genuine header and signature, forged claims
+        let mut parts: Vec<&str> = token.split('.').collect();
+        parts[1] = forged.split('.').nth(1).unwrap();
+        let tampered = parts.join(".");
+        
+        let requirements = TokenRequirements::new();
+        assert!(!verifier.verify(&tampered, &requirements).await.unwrap().valid);
+        assert!(!verifier.verify(&forged, &requirements).await.unwrap().valid);
+    }
+    
+    #[tokio::test]
+    async fn test_malformed_token_rejected_without_network() {
+        let mut server = Server::new_async().await;
+        let keys = mock_keys(&mut server, &[], 0).await;
+        
+        let verifier = verifier(&server);
+        for token in ["", "not-a-token", "a.b.c", "a.b"] {
+            assert!(!verifier.verify(token, &TokenRequirements::new()).await.unwrap().valid);
+        }
+        keys.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_missing_scope_rejected() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let _keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&key, 1, &claims(&["read:users"], now() + 300));
+        let requirements = TokenRequirements::new().scope("read:users").scope("write:users");
+        
+        assert!(!verifier.verify(&token, &requirements).await.unwrap().valid);
+    }
+    
+    #[tokio::test]
+    async fn test_channel_binding() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let _keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&key, 1, &claims(&[], now() + 300));
+        
+        let bound = TokenRequirements::new().channel_binding([7u8; 32]);
+        assert!(verifier.verify(&token, &bound).await.unwrap().valid);
+        
+        let other_channel = TokenRequirements::new().channel_binding([8u8; 32]);
+        assert!(!verifier.verify(&token, &other_channel).await.unwrap().valid);
+        
+        let mut unbound_claims = claims(&[], now() + 300);
+        unbound_claims.as_object_mut().unwrap().remove("cb");
+        let unbound = mint(&key, 1, &unbound_claims);
+        assert!(!verifier.verify(&unbound, &bound).await.unwrap().valid);
+    }
+    
+    #[tokio::test]
+    async fn test_rotated_key_refetched() {
+        let mut server = Server::new_async().await;
+        let old_key = signing_key(1);
+        let new_key = signing_key(2);
+        
+        let verifier = verifier(&server).key_refresh_interval(std::time::Duration::ZERO);
+        
+        let old_keys = mock_keys(&mut server, &[(1, &old_key)], 1).await;
+        let token = mint(&old_key, 1, &claims(&[], now() + 300));
+        assert!(verifier.verify(&token, &TokenRequirements::new()).await.unwrap().valid);
+        old_keys.assert_async().await;
+        old_keys.remove_async().await;
+        
+        let new_keys = mock_keys(&mut server, &[(2, &new_key)], 1).await;
+        let token = mint(&new_key, 2, &claims(&[], now() + 300));
+        assert!(verifier.verify(&token, &TokenRequirements::new()).await.unwrap().valid);
+        new_keys.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_forged_token_with_unknown_kid_rejected() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        // A server answer for the copied `jti` must not be consulted
+        let validate = mock_validate(
+            &mut server,
+            json!({"valid": true, "subject": "alice", "scope": ["read:users"]}),
+            0,
+        ).await;
+        
+        let verifier = verifier(&server);
+        let engine = base64::engine::general_purpose::URL_SAFE_NO_PAD;
+        let forged = format!(
+            "{}.{}.{}",
+            engine.encode(json!({"alg": "EdDSA", "kid": 99}).to_string()),
+            engine.encode(claims(&["read:users"], now() + 300).to_string()),
+            engine.encode([0u8; 64]),
+        );
+        
+        let result = verifier.verify(&forged, &TokenRequirements::new().scope("read:users")).await.unwrap();
+        assert!(!result.valid);
+        assert_eq!(result.subject, None);
+        keys.assert_async().await;
+        validate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_unknown_kid_validated_by_server_when_enabled() {
+        let mut server = Server::new_async().await;
+        let published = signing_key(1);
+        let unpublished = signing_key(2);
+        let _keys = mock_keys(&mut server, &[(1, &published)], 1).await;
+        let token = mint(&unpublished, 2, &claims(&["read:users"], now() + 300));
+        let validate = server.mock("POST", "/v1/validate")
+            .match_body(mockito::Matcher::Json(json!({ "token": token })))
+            .with_status(200)
+            .with_body(r#"{"valid":true,"subject":"alice","scope":["read:users"]}"#)
+            .expect(2)
+            .create_async()
+            .await;
+        
+        let verifier = verifier(&server).server_fallback(true);
+        
+        let result = verifier.verify(&token, &TokenRequirements::new().scope("read:users")).await.unwrap();
+        assert!(result.valid);
+        assert_eq!(result.subject.as_deref(), Some("alice"));
+        // The server vouches for the token, but the requirements still apply
+        assert!(!verifier.verify(&token, &TokenRequirements::new().scope("write:users")).await.unwrap().valid);
+        validate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_revocation_check() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let _keys = mock_keys(&mut server, &[(1, &key)], 1).await;
+        let validate = mock_validate(&mut server, json!({"valid": false}), 1).await;
+        
+        let verifier = verifier(&server).check_revocation(true);
+        let token = mint(&key, 1, &claims(&[], now() + 300));
+        
+        assert!(!verifier.verify(&token, &TokenRequirements::new()).await.unwrap().valid);
+        validate.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_key_fetch_failure_is_error() {
+        let mut server = Server::new_async().await;
+        let _keys = server.mock("GET", "/v1/tokens/keys")
+            .with_status(503)
+            .create_async()
+            .await;
+        
+        let verifier = verifier(&server);
+        let token = mint(&signing_key(1), 1, &claims(&[], now() + 300));
+        
+        assert!(matches!(
+            verifier.verify(&token, &TokenRequirements::new()).await,
+            Err(TruthlinkedError::ServerError)
+        ));
+    }
+}
+
+#[cfg(test)]
+mod manager_tests {
+    use super::*;
+    use std::time::Duration;
+    
+    async fn mock_exchange(server: &mut ServerGuard, af_token: &str, expires_at: u64, hits: usize) -> Mock {
+        server.mock("POST", "/v1/tokens")
+            .with_status(200)
+            .with_body(json!({
+                "af_token": af_token,
+                "granted_scope": ["read:users"],
+                "expires_at": expires_at,
+                "exchange_id": "tok_1",
+            }).to_string())
+            .expect(hits)
+            .create_async()
+            .await
+    }
+    
+    fn manager(server: &ServerGuard) -> TokenManager {
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        TokenManager::new(Arc::new(client), [7u8; 32])
+    }
+    
+    fn scope(items: &[&str]) -> Vec<String> {
+        items.iter().map(|s| s.to_string()).collect()
+    }
+    
+    #[tokio::test]
+    async fn test_cached_token_reused() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 3600, 1).await;
+        
+        let manager = manager(&server);
+        for _ in 0..5 {
+            let token = manager.token("alice", "sso", &scope(&["read:users"])).await.unwrap();
+            assert_eq!(token.as_str(), "af_1");
+            assert_eq!(token.exchange_id(), "tok_1");
+        }
+        
+        exchange.assert_async().await;
+        assert_eq!(manager.len(), 1);
+    }
+    
+    #[tokio::test]
+    async fn test_scope_order_ignored() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 3600, 1).await;
+        
+        let manager = manager(&server);
+        manager.token("alice", "sso", &scope(&["a", "b"])).await.unwrap();
+        manager.token("alice", "sso", &scope(&["b", "a", "a"])).await.unwrap();
+        
+        exchange.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_keyed_by_subject_and_scope() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 3600, 3).await;
+        
+        let manager = manager(&server);
+        manager.token("alice", "sso", &scope(&["a"])).await.unwrap();
+        manager.token("alice", "sso", &scope(&["b"])).await.unwrap();
+        manager.token("bob", "sso", &scope(&["a"])).await.unwrap();
+        
+        exchange.assert_async().await;
+        assert_eq!(manager.len(), 3);
+    }
+    
+    #[tokio::test]
+    async fn test_refreshed_within_skew() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 30, 2).await;
+        
+        let manager = manager(&server).refresh_skew(Duration::from_secs(60));
+        manager.token("alice", "sso", &[]).await.unwrap();
+        manager.token("alice", "sso", &[]).await.unwrap();
+        
+        exchange.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_concurrent_refresh_single_flight() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 3600, 1).await;
+        
+        let manager = Arc::new(manager(&server));
+        let tasks: Vec<_> = (0..20)
+            .map(|_| {
+                let manager = manager.clone();
+                tokio::spawn(async move {
+                    manager.token("alice", "sso", &scope(&["read:users"])).await.unwrap()
+                })
+            })
+            .collect();
+        
+        for task in tasks {
+            assert_eq!(task.await.unwrap().as_str(), "af_1");
+        }
+        exchange.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_failed_refresh_keeps_unexpired_token() {
+        let mut server = Server::new_async().await;
+        let manager = manager(&server).refresh_skew(Duration::from_secs(600));
+        
+        let exchange = mock_exchange(&mut server, "af_1", now() + 300, 1).await;
+        manager.token("alice", "sso", &[]).await.unwrap();
+        exchange.assert_async().await;
+        exchange.remove_async().await;
+        
+        let _failing = server.mock("POST", "/v1/tokens")
+            .with_status(503)
+            .create_async()
+            .await;
+        let token = manager.token("alice", "sso", &[]).await.unwrap();
+        assert_eq!(token.as_str(), "af_1");
+    }
+    
+    #[tokio::test]
+    async fn test_exchange_error_propagates() {
+        let mut server = Server::new_async().await;
+        let _exchange = server.mock("POST", "/v1/tokens")
+            .with_status(403)
+            .create_async()
+            .await;
+        
+        let manager = manager(&server);
+        assert!(matches!(
+            manager.token("alice", "sso", &[]).await,
+            Err(TruthlinkedError::Forbidden)
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_invalidate() {
+        let mut server = Server::new_async().await;
+        let exchange = mock_exchange(&mut server, "af_1", now() + 3600, 2).await;
+        
+        let manager = manager(&server);
+        manager.token("alice", "sso", &scope(&["a", "b"])).await.unwrap();
+        manager.invalidate("alice", &scope(&["b", "a"]));
+        assert!(manager.is_empty());
+        manager.token("alice", "sso", &scope(&["a", "b"])).await.unwrap();
+        
+        exchange.assert_async().await;
+    }
+    
+    #[test]
+    fn test_token_debug_redacted() {
+        let token = AfToken::from(TokenResponse {
+            af_token: "secret_af_token".to_string(),
+            granted_scope: vec![],
+            expires_at: 0,
+            exchange_id: "tok_1".to_string(),
+        });
+        
+        let debug = format!("{:?}", token);
+        assert!(!debug.contains("secret_af_token"));
+        assert!(debug.contains("tok_1"));
+    }
+}
+
+#[cfg(test)]
+mod binding_tests {
+    use super::*;
+    
+    #[test]
+    fn test_nonces_unique() {
+        let nonces = NonceGenerator::new();
+        let generated: std::collections::HashSet<[u8; 32]> = (0..1000).map(|_| nonces.generate()).collect();
+        
+        assert_eq!(generated.len(), 1000);
+        assert_eq!(nonces.len(), 1000);
+    }
+    
+    #[test]
+    fn test_replayed_nonce_rejected() {
+        let nonces = NonceGenerator::new();
+        let peer_nonce = [9u8; 32];
+        
+        assert!(nonces.check_and_record(&peer_nonce));
+        assert!(!nonces.check_and_record(&peer_nonce));
+        assert!(!nonces.check_and_record(&nonces.generate()));
+    }
+    
+    #[test]
+    fn test_tls_exporter_binding() {
+        let binding = ChannelBinding::from_tls_exporter(&[3u8; 32]).unwrap();
+        assert_eq!(binding.as_bytes(), &[3u8; 32]);
+        
+        assert!(matches!(
+            ChannelBinding::from_tls_exporter(&[3u8; 48]),
+            Err(TruthlinkedError::InvalidRequest(_))
+        ));
+    }
+    
+    #[test]
+    fn test_certificate_der_binding_is_sha256() {
+        let binding: [u8; 32] = ChannelBinding::from_certificate_der(b"abc").into();
+        
+        assert_eq!(
+            hex::encode(binding),
+            "ba7816bf8f01cfea414140de5dae2223b00361a396177a9cb410ff61f20015ad"
+        );
+    }
+    
+    #[test]
+    fn test_certificate_pem_matches_der() {
+        let der = vec![0x30u8; 100];
+        let body = base64::engine::general_purpose::STANDARD.encode(&der);
+        let pem = format!(
+            "-----BEGIN CERTIFICATE-----\n{}\n{}\n-----END CERTIFICATE-----\n-----BEGIN CERTIFICATE-----\nAAAA\n-----END CERTIFICATE-----\n",
+            &body[..64],
+            &body[64..]
+        );
+        
+        assert_eq!(
+            ChannelBinding::from_certificate_pem(&pem).unwrap(),
+            ChannelBinding::from_certificate_der(&der)
+        );
+        assert!(ChannelBinding::from_certificate_pem("not a certificate").is_err());
+    }
+    
+    #[tokio::test]
+    async fn test_manager_sends_derived_binding() {
+        let mut server = Server::new_async().await;
+        let binding = ChannelBinding::from_certificate_der(b"server certificate");
+        let exchange = server.mock("POST", "/v1/tokens")
+            .match_body(mockito::Matcher::PartialJson(json!({
+                "channel_binding": hex::encode(binding.as_bytes()),
+            })))
+            .with_status(200)
+            .with_body(json!({
+                "af_token": "af_1",
+                "granted_scope": [],
+                "expires_at": now() + 3600,
+                "exchange_id": "tok_1",
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key").build().unwrap();
+        let manager = TokenManager::new(Arc::new(client), binding);
+        manager.token("alice", "sso", &[]).await.unwrap();
+        
+        exchange.assert_async().await;
+    }
+}
@@ -12,7 +12,7 @@ mod error_tests {
         let error = TruthlinkedError::Forbidden;
         assert_eq!(error.to_string(), "Access denied: insufficient tier permissions");
         
-        let error = TruthlinkedError::RateLimitExceeded("Too many requests".to_string());
+        let error = TruthlinkedError::RateLimitExceeded("Too many requests".into());
         assert_eq!(error.to_string(), "Rate limit exceeded: Too many requests");
     }
     
@@ -126,4 +126,19 @@ mod types_tests {
         assert_eq!(parsed.requested_scope.len(), 2);
         assert_eq!(parsed.nonce, "deadbeef");
     }
+    
+    #[test]
+    fn test_shadow_decision_new() {
+        let mut decision = ShadowDecision::new("d1", true, false);
+        decision.principal = Some("alice".to_string());
+        
+        assert!(decision.breach_prevented);
+        assert_eq!(decision.divergence(), Some(DivergenceDirection::IamAllowedAfDenied));
+        
+        let json = serde_json::to_value(&decision).unwrap();
+        assert_eq!(json["principal"], "alice");
+        assert!(json.get("action").is_none());
+        assert!(json.get("timestamp").is_none());
+        assert!(!ShadowDecision::new("d2", false, true).breach_prevented);
+    }
 }
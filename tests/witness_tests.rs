@@ -0,0 +1,805 @@
+use truthlinked_sdk::witness::{self, Hash, InclusionProof};
+use truthlinked_sdk::*;
+
+/// Reference Merkle tree hash (RFC 6962 section 2.1)
+fn tree_root(leaves: &[Hash]) -> Hash {
+    match leaves.len() {
+        0 => panic!("empty tree"),
+        1 => leaves[0],
+        n => {
+            let k = split_point(n);
+            witness::node_hash(&tree_root(&leaves[..k]), &tree_root(&leaves[k..]))
+        }
+    }
+}
+
+/// Reference audit path (RFC 6962 section 2.1.1)
+fn audit_path(index: usize, leaves: &[Hash]) -> Vec<Hash> {
+    if leaves.len() <= 1 {
+        return Vec::new();
+    }
+    let k = split_point(leaves.len());
+    if index < k {
+        let mut path = audit_path(index, &leaves[..k]);
+        path.push(tree_root(&leaves[k..]));
+        path
+    } else {
+        let mut path = audit_path(index - k, &leaves[k..]);
+        path.push(tree_root(&leaves[..k]));
+        path
+    }
+}
+
+/// Reference consistency proof (RFC 6962 section 2.1.2)
+fn consistency_path(m: usize, leaves: &[Hash]) -> Vec<Hash> {
+    subproof(m, leaves, true)
+}
+
+fn subproof(m: usize, leaves: &[Hash], complete: bool) -> Vec<Hash> {
+    let n = leaves.len();
+    if m == n {
+        return if complete { Vec::new() } else { vec![tree_root(leaves)] };
+    }
+    let k = split_point(n);
+    if m <= k {
+        let mut path = subproof(m, &leaves[..k], complete);
+        path.push(tree_root(&leaves[k..]));
+        path
+    } else {
+        let mut path = subproof(m - k, &leaves[k..], false);
+        path.push(tree_root(&leaves[..k]));
+        path
+    }
+}
+
+/// Largest power of two smaller than `n`
+fn split_point(n: usize) -> usize {
+    let mut k = 1;
+    while k * 2 < n {
+        k *= 2;
+    }
+    k
+}
+
+fn event_hashes(count: usize) -> Vec<[u8; 32]> {
+    (0..count).map(|i| [i as u8; 32]).collect()
+}
+
+fn leaves(hashes: &[[u8; 32]]) -> Vec<Hash> {
+    hashes.iter().map(|h| witness::leaf_hash(h)).collect()
+}
+
+fn tree_head(tree_size: u64, root: Hash) -> SignedTreeHead {
+    SignedTreeHead {
+        tree_size,
+        timestamp: 1_700_000_000,
+        root_hash: hex::encode(root),
+        signature: String::new(),
+        key_version: 1,
+    }
+}
+
+fn witness_event(sequence: u64, event_hash: &[u8; 32], proof: Option<String>) -> WitnessEvent {
+    WitnessEvent {
+        sequence,
+        timestamp: 1_700_000_000,
+        submission: WitnessSubmission {
+            af_event_hash: String::new(),
+            af_merkle_root: String::new(),
+            af_sequence: sequence,
+            af_instance_id: "af-1".to_string(),
+            oracle_time: 1_700_000_000,
+            af_signature: String::new(),
+        },
+        prev_hash: String::new(),
+        event_hash: hex::encode(event_hash),
+        proof,
+    }
+}
+
+fn consistency_proof(first_size: u64, second_size: u64, path: &[Hash]) -> ConsistencyProof {
+    ConsistencyProof {
+        first_size,
+        second_size,
+        proof: path.iter().map(hex::encode).collect(),
+    }
+}
+
+fn proof_json(leaf_index: u64, tree_size: u64, path: &[Hash]) -> String {
+    serde_json::to_string(&InclusionProof {
+        leaf_index,
+        tree_size,
+        audit_path: path.iter().map(hex::encode).collect(),
+    })
+    .unwrap()
+}
+
+#[cfg(test)]
+mod inclusion_tests {
+    use super::*;
+    
+    #[test]
+    fn test_inclusion_every_leaf_every_size() {
+        let hashes = event_hashes(13);
+        
+        for size in 1..=hashes.len() {
+            let leaves = leaves(&hashes[..size]);
+            let sth = tree_head(size as u64, tree_root(&leaves));
+            
+            for (index, hash) in hashes[..size].iter().enumerate() {
+                let path = audit_path(index, &leaves);
+                let proof = proof_json(index as u64, size as u64, &path);
+                let event = witness_event(index as u64, hash, Some(proof));
+                
+                assert_eq!(witness::verify_inclusion(&event, &sth), Ok(()), "size {} index {}", size, index);
+            }
+        }
+    }
+    
+    #[test]
+    fn test_inclusion_missing_proof() {
+        let hashes = event_hashes(4);
+        let sth = tree_head(4, tree_root(&leaves(&hashes)));
+        let event = witness_event(0, &hashes[0], None);
+        
+        assert_eq!(witness::verify_inclusion(&event, &sth), Err(VerificationError::MissingProof));
+    }
+    
+    #[test]
+    fn test_inclusion_malformed_proof() {
+        let hashes = event_hashes(4);
+        let sth = tree_head(4, tree_root(&leaves(&hashes)));
+        let event = witness_event(0, &hashes[0], Some("not json".to_string()));
+        
+        assert_eq!(witness::verify_inclusion(&event, &sth), Err(VerificationError::MalformedProof));
+    }
+    
+    #[test]
+    fn test_inclusion_tampered_event_hash() {
+        let hashes = event_hashes(6);
+        let leaves = leaves(&hashes);
+        let sth = tree_head(6, tree_root(&leaves));
+        let proof = proof_json(2, 6, &audit_path(2, &leaves));
+        let event = witness_event(2, &[0xff; 32], Some(proof));
+        
+        match witness::verify_inclusion(&event, &sth) {
+            Err(VerificationError::RootMismatch { expected, .. }) => assert_eq!(expected, sth.root_hash),
+            other => panic!("Expected RootMismatch, got {:?}", other),
+        }
+    }
+    
+    #[test]
+    fn test_inclusion_tree_size_mismatch() {
+        let hashes = event_hashes(6);
+        let leaves = leaves(&hashes);
+        let sth = tree_head(6, tree_root(&leaves));
+        let proof = proof_json(2, 5, &audit_path(2, &leaves[..5]));
+        let event = witness_event(2, &hashes[2], Some(proof));
+        
+        assert_eq!(
+            witness::verify_inclusion(&event, &sth),
+            Err(VerificationError::TreeSizeMismatch { proof: 5, tree_head: 6 })
+        );
+    }
+    
+    #[test]
+    fn test_inclusion_wrong_path_length() {
+        let hashes = event_hashes(8);
+        let leaves = leaves(&hashes);
+        let sth = tree_head(8, tree_root(&leaves));
+        let mut path = audit_path(3, &leaves);
+        path.pop();
+        let event = witness_event(3, &hashes[3], Some(proof_json(3, 8, &path)));
+        
+        assert!(matches!(
+            witness::verify_inclusion(&event, &sth),
+            Err(VerificationError::InvalidPathLength { path_length: 2, .. })
+        ));
+    }
+    
+    #[test]
+    fn test_inclusion_invalid_hex() {
+        let hashes = event_hashes(2);
+        let leaves = leaves(&hashes);
+        let mut sth = tree_head(2, tree_root(&leaves));
+        sth.root_hash = "zz".to_string();
+        let event = witness_event(0, &hashes[0], Some(proof_json(0, 2, &audit_path(0, &leaves))));
+        
+        assert_eq!(
+            witness::verify_inclusion(&event, &sth),
+            Err(VerificationError::InvalidHash("root_hash"))
+        );
+    }
+    
+    #[test]
+    fn test_verification_error_converts() {
+        let error: TruthlinkedError = VerificationError::MissingProof.into();
+        assert_eq!(error.to_string(), "Verification failed: event does not carry an inclusion proof");
+    }
+}
+
+#[cfg(test)]
+mod consistency_tests {
+    use super::*;
+    use mockito::Server;
+    use serde_json::json;
+    
+    #[test]
+    fn test_consistency_all_size_pairs() {
+        let leaves = leaves(&event_hashes(17));
+        
+        for new_size in 1..=leaves.len() {
+            let new = tree_head(new_size as u64, tree_root(&leaves[..new_size]));
+            
+            for old_size in 1..new_size {
+                let old = tree_head(old_size as u64, tree_root(&leaves[..old_size]));
+                let path = consistency_path(old_size, &leaves[..new_size]);
+                let proof = consistency_proof(old_size as u64, new_size as u64, &path);
+                
+                assert_eq!(
+                    witness::verify_consistency(&old, &new, &proof),
+                    Ok(()),
+                    "old {} new {}",
+                    old_size,
+                    new_size
+                );
+            }
+        }
+    }
+    
+    #[test]
+    fn test_consistency_detects_rewritten_history() {
+        let leaves = leaves(&event_hashes(10));
+        let mut forked = leaves.clone();
+        forked[2] = witness::leaf_hash(&[0xaa; 32]);
+        
+        let old = tree_head(5, tree_root(&leaves[..5]));
+        let new = tree_head(10, tree_root(&forked));
+        let proof = consistency_proof(5, 10, &consistency_path(5, &forked));
+        
+        assert!(matches!(
+            witness::verify_consistency(&old, &new, &proof),
+            Err(VerificationError::OldRootMismatch { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_consistency_detects_tampered_new_root() {
+        let leaves = leaves(&event_hashes(10));
+        let old = tree_head(6, tree_root(&leaves[..6]));
+        let new = tree_head(10, [0u8; 32]);
+        let proof = consistency_proof(6, 10, &consistency_path(6, &leaves));
+        
+        assert!(matches!(
+            witness::verify_consistency(&old, &new, &proof),
+            Err(VerificationError::RootMismatch { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_consistency_rejects_shrinking_tree() {
+        let leaves = leaves(&event_hashes(8));
+        let old = tree_head(8, tree_root(&leaves));
+        let new = tree_head(4, tree_root(&leaves[..4]));
+        let proof = consistency_proof(8, 4, &[]);
+        
+        assert_eq!(
+            witness::verify_consistency(&old, &new, &proof),
+            Err(VerificationError::TreeShrunk { old: 8, new: 4 })
+        );
+    }
+    
+    #[test]
+    fn test_consistency_same_size_split_view() {
+        let leaves = leaves(&event_hashes(5));
+        let old = tree_head(5, tree_root(&leaves));
+        let same = tree_head(5, tree_root(&leaves));
+        let split = tree_head(5, [7u8; 32]);
+        let proof = consistency_proof(5, 5, &[]);
+        
+        assert_eq!(witness::verify_consistency(&old, &same, &proof), Ok(()));
+        assert!(matches!(
+            witness::verify_consistency(&old, &split, &proof),
+            Err(VerificationError::RootMismatch { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_consistency_truncated_path() {
+        let leaves = leaves(&event_hashes(12));
+        let old = tree_head(5, tree_root(&leaves[..5]));
+        let new = tree_head(12, tree_root(&leaves));
+        let mut path = consistency_path(5, &leaves);
+        path.pop();
+        
+        assert_eq!(
+            witness::verify_consistency(&old, &new, &consistency_proof(5, 12, &path)),
+            Err(VerificationError::InvalidConsistencyPath)
+        );
+    }
+    
+    #[tokio::test]
+    async fn test_get_consistency_proof() {
+        let mut server = Server::new_async().await;
+        
+        let mock = server.mock("GET", "/witness/consistency")
+            .match_query(mockito::Matcher::AllOf(vec![
+                mockito::Matcher::UrlEncoded("first".into(), "4".into()),
+                mockito::Matcher::UrlEncoded("second".into(), "10".into()),
+            ]))
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(json!({
+                "firstSize": 4,
+                "secondSize": 10,
+                "proof": [hex::encode([1u8; 32])]
+            }).to_string())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .build()
+            .unwrap();
+        
+        let proof = client.get_consistency_proof(4, 10).await.unwrap();
+        
+        assert_eq!(proof.first_size, 4);
+        assert_eq!(proof.second_size, 10);
+        assert_eq!(proof.proof.len(), 1);
+        mock.assert_async().await;
+    }
+}
+
+#[cfg(test)]
+mod keyring_tests {
+    use super::*;
+    use base64::Engine;
+    use ed25519_dalek::pkcs8::{spki::der::pem::LineEnding, EncodePublicKey};
+    use ed25519_dalek::{Signer, SigningKey};
+    use mockito::Server;
+    use truthlinked_sdk::witness::WitnessKeyring;
+    
+    fn signing_key(seed: u8) -> SigningKey {
+        SigningKey::from_bytes(&[seed; 32])
+    }
+    
+    fn signed_head(key: &SigningKey, key_version: u32, tree_size: u64) -> SignedTreeHead {
+        let mut sth = tree_head(tree_size, [tree_size as u8; 32]);
+        sth.key_version = key_version;
+        let signature = key.sign(&witness::sth_signing_payload(&sth));
+        sth.signature = base64::engine::general_purpose::STANDARD.encode(signature.to_bytes());
+        sth
+    }
+    
+    fn keyring_with(key: &SigningKey, key_version: u32) -> WitnessKeyring {
+        let mut keyring = WitnessKeyring::new();
+        keyring.add_key(key_version, key.verifying_key().as_bytes()).unwrap();
+        keyring
+    }
+    
+    #[test]
+    fn test_sth_signing_payload_format() {
+        let sth = tree_head(42, [0xab; 32]);
+        let payload = String::from_utf8(witness::sth_signing_payload(&sth)).unwrap();
+        assert_eq!(payload, format!("42\n1700000000\n{}", "ab".repeat(32)));
+    }
+    
+    #[test]
+    fn test_verify_sth_valid() {
+        let key = signing_key(1);
+        let keyring = keyring_with(&key, 3);
+        
+        assert_eq!(keyring.verify_sth(&signed_head(&key, 3, 10)), Ok(()));
+    }
+    
+    #[test]
+    fn test_verify_sth_tampered() {
+        let key = signing_key(1);
+        let keyring = keyring_with(&key, 1);
+        let mut sth = signed_head(&key, 1, 10);
+        sth.tree_size = 11;
+        
+        assert_eq!(keyring.verify_sth(&sth), Err(VerificationError::InvalidSignature));
+    }
+    
+    #[test]
+    fn test_verify_sth_wrong_key() {
+        let keyring = keyring_with(&signing_key(1), 1);
+        let sth = signed_head(&signing_key(2), 1, 10);
+        
+        assert_eq!(keyring.verify_sth(&sth), Err(VerificationError::InvalidSignature));
+    }
+    
+    #[test]
+    fn test_verify_sth_unknown_version() {
+        let key = signing_key(1);
+        let keyring = keyring_with(&key, 1);
+        
+        assert_eq!(
+            keyring.verify_sth(&signed_head(&key, 2, 10)),
+            Err(VerificationError::UnknownKeyVersion(2))
+        );
+    }
+    
+    #[test]
+    fn test_verify_sth_malformed_signature() {
+        let key = signing_key(1);
+        let keyring = keyring_with(&key, 1);
+        let mut sth = signed_head(&key, 1, 10);
+        sth.signature = "not base64!".to_string();
+        
+        assert_eq!(keyring.verify_sth(&sth), Err(VerificationError::MalformedSignature));
+    }
+    
+    #[test]
+    fn test_keyring_from_json_mixed_encodings() {
+        let old_key = signing_key(1);
+        let new_key = signing_key(2);
+        let pem = new_key.verifying_key().to_public_key_pem(LineEnding::LF).unwrap();
+        let json = serde_json::json!({
+            "keys": [
+                {
+                    "keyVersion": 1,
+                    "publicKey": base64::engine::general_purpose::STANDARD.encode(old_key.verifying_key().as_bytes())
+                },
+                { "keyVersion": 2, "publicKey": pem }
+            ]
+        });
+        
+        let keyring = WitnessKeyring::from_json(&json.to_string()).unwrap();
+        
+        assert_eq!(keyring.len(), 2);
+        assert_eq!(keyring.verify_sth(&signed_head(&old_key, 1, 5)), Ok(()));
+        assert_eq!(keyring.verify_sth(&signed_head(&new_key, 2, 6)), Ok(()));
+    }
+    
+    #[test]
+    fn test_keyring_from_pem_file() {
+        let key = signing_key(9);
+        let pem = key.verifying_key().to_public_key_pem(LineEnding::LF).unwrap();
+        let path = std::env::temp_dir().join(format!("truthlinked-witness-key-{}.pem", std::process::id()));
+        std::fs::write(&path, pem).unwrap();
+        
+        let keyring = WitnessKeyring::from_pem_file(4, &path).unwrap();
+        std::fs::remove_file(&path).unwrap();
+        
+        assert!(keyring.contains(4));
+        assert_eq!(keyring.verify_sth(&signed_head(&key, 4, 1)), Ok(()));
+    }
+    
+    #[test]
+    fn test_keyring_rejects_invalid_key() {
+        let mut keyring = WitnessKeyring::new();
+        
+        assert!(matches!(
+            keyring.add_key(1, &[0u8; 5]),
+            Err(TruthlinkedError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            WitnessKeyring::from_json(r#"{"keys":[{"keyVersion":1,"publicKey":"@@@"}]}"#),
+            Err(TruthlinkedError::InvalidRequest(_))
+        ));
+    }
+    
+    #[tokio::test]
+    async fn test_client_refuses_unverifiable_sth() {
+        let mut server = Server::new_async().await;
+        let trusted = signing_key(1);
+        let forged = signed_head(&signing_key(2), 1, 10);
+        
+        let mock = server.mock("GET", "/witness/sth/latest")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&forged).unwrap())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .witness_keyring(keyring_with(&trusted, 1))
+            .build()
+            .unwrap();
+        
+        match client.get_latest_sth().await.unwrap_err() {
+            TruthlinkedError::Verification(VerificationError::InvalidSignature) => {}
+            e => panic!("Expected InvalidSignature, got {:?}", e),
+        }
+        mock.assert_async().await;
+    }
+    
+    #[tokio::test]
+    async fn test_client_accepts_verified_sth() {
+        let mut server = Server::new_async().await;
+        let key = signing_key(1);
+        let sth = signed_head(&key, 1, 10);
+        
+        let mock = server.mock("GET", "/witness/sth/10")
+            .with_status(200)
+            .with_header("content-type", "application/json")
+            .with_body(serde_json::to_string(&sth).unwrap())
+            .create_async()
+            .await;
+        
+        let client = ClientBuilder::testing(server.url(), "test_key")
+            .witness_keyring(keyring_with(&key, 1))
+            .build()
+            .unwrap();
+        
+        let fetched = client.get_sth(10).await.unwrap();
+        
+        assert_eq!(fetched.root_hash, sth.root_hash);
+        mock.assert_async().await;
+    }
+}
+
+#[cfg(test)]
+mod export_tests {
+    use super::*;
+    use truthlinked_sdk::witness::{ChainVerifier, MerkleAccumulator, GENESIS_PREV_HASH};
+    
+    fn chain(count: u64) -> Vec<WitnessEvent> {
+        let mut events = Vec::new();
+        let mut prev_hash = GENESIS_PREV_HASH.to_string();
+        
+        for sequence in 0..count {
+            let mut event = witness_event(sequence, &[0u8; 32], None);
+            event.prev_hash = prev_hash;
+            event.submission.af_event_hash = hex::encode([sequence as u8; 32]);
+            event.event_hash = hex::encode(witness::compute_event_hash(&event));
+            prev_hash = event.event_hash.clone();
+            events.push(event);
+        }
+        events
+    }
+    
+    fn bundle(events: &[WitnessEvent]) -> Vec<u8> {
+        events
+            .iter()
+            .map(|event| serde_json::to_string(event).unwrap() + "\n")
+            .collect::<String>()
+            .into_bytes()
+    }
+    
+    fn head_for(events: &[WitnessEvent]) -> SignedTreeHead {
+        let leaves: Vec<Hash> = events
+            .iter()
+            .map(|event| witness::leaf_hash(&hex::decode(&event.event_hash).unwrap()))
+            .collect();
+        tree_head(events.len() as u64, tree_root(&leaves))
+    }
+    
+    #[test]
+    fn test_accumulator_matches_reference_tree() {
+        let leaves = leaves(&event_hashes(33));
+        let mut accumulator = MerkleAccumulator::new();
+        
+        for (count, leaf) in leaves.iter().enumerate() {
+            accumulator.push(*leaf);
+            assert_eq!(accumulator.root(), tree_root(&leaves[..=count]), "size {}", count + 1);
+        }
+    }
+    
+    #[test]
+    fn test_parse_export_skips_blank_lines() {
+        let events = chain(3);
+        let mut data = bundle(&events);
+        data.extend_from_slice(b"\n\n");
+        
+        let parsed: Vec<WitnessEvent> = witness::parse_export(&data)
+            .collect::<std::result::Result<_, _>>()
+            .unwrap();
+        
+        assert_eq!(parsed.len(), 3);
+        assert_eq!(parsed[2].event_hash, events[2].event_hash);
+    }
+    
+    #[test]
+    fn test_parse_export_reports_malformed_line() {
+        let mut data = bundle(&chain(2));
+        data.extend_from_slice(b"{not json}\n");
+        
+        let results: Vec<_> = witness::parse_export(&data).collect();
+        
+        assert_eq!(results.len(), 3);
+        assert!(matches!(results[2], Err(VerificationError::MalformedExport { line: 3 })));
+    }
+    
+    #[test]
+    fn test_verify_export_valid() {
+        let events = chain(11);
+        
+        let summary = witness::verify_export(&bundle(&events), &head_for(&events)).unwrap();
+        
+        assert_eq!(summary.events, 11);
+        assert_eq!(summary.first_sequence, Some(0));
+        assert_eq!(summary.last_sequence, Some(10));
+        assert_eq!(summary.head_hash.as_deref(), Some(events[10].event_hash.as_str()));
+    }
+    
+    #[test]
+    fn test_verify_export_tampered_submission() {
+        let mut events = chain(5);
+        let sth = head_for(&events);
+        events[3].submission.af_instance_id = "attacker".to_string();
+        
+        assert_eq!(
+            witness::verify_export(&bundle(&events), &sth),
+            Err(VerificationError::EventHashMismatch { sequence: 3 })
+        );
+    }
+    
+    #[test]
+    fn test_verify_export_missing_event() {
+        let mut events = chain(5);
+        let sth = head_for(&events);
+        events.remove(2);
+        
+        assert_eq!(
+            witness::verify_export(&bundle(&events), &sth),
+            Err(VerificationError::SequenceGap { expected: 2, found: 3 })
+        );
+    }
+    
+    #[test]
+    fn test_verify_export_broken_link() {
+        let mut events = chain(4);
+        let sth = head_for(&events);
+        events[2].prev_hash = hex::encode([9u8; 32]);
+        events[2].event_hash = hex::encode(witness::compute_event_hash(&events[2]));
+        
+        assert_eq!(
+            witness::verify_export(&bundle(&events), &sth),
+            Err(VerificationError::ChainBroken { sequence: 2 })
+        );
+    }
+    
+    #[test]
+    fn test_verify_export_root_mismatch() {
+        let events = chain(6);
+        let mut sth = head_for(&events);
+        sth.root_hash = hex::encode([1u8; 32]);
+        
+        assert!(matches!(
+            witness::verify_export(&bundle(&events), &sth),
+            Err(VerificationError::RootMismatch { .. })
+        ));
+    }
+    
+    #[test]
+    fn test_verify_export_event_count_mismatch() {
+        let events = chain(6);
+        let sth = head_for(&events[..4]);
+        
+        assert_eq!(
+            witness::verify_export(&bundle(&events), &sth),
+            Err(VerificationError::EventCountMismatch { events: 6, tree_size: 4 })
+        );
+    }
+    
+    #[test]
+    fn test_chain_verifier_resumes_segment() {
+        let events = chain(8);
+        let mut verifier = ChainVerifier::resume_after(4, events[4].event_hash.clone()).unwrap();
+        
+        for event in &events[5..] {
+            verifier.push(event).unwrap();
+        }
+        
+        assert_eq!(verifier.summary().events, 3);
+        assert_eq!(verifier.finish(&head_for(&events)), Err(VerificationError::PartialChain));
+    }
+    
+    #[test]
+    fn test_chain_verifier_resume_after_last_sequence() {
+        assert_eq!(
+            ChainVerifier::resume_after(u64::MAX, "00".repeat(32)).err(),
+            Some(VerificationError::SequenceOverflow { sequence: u64::MAX })
+        );
+    }
+}
+
+#[cfg(test)]
+mod submission_tests {
+    use super::*;
+    use base64::Engine;
+    use ed25519_dalek::pkcs8::{spki::der::pem::LineEnding, EncodePrivateKey};
+    use ed25519_dalek::{Signature, SigningKey, Verifier};
+    use sha2::{Digest, Sha256};
+    use truthlinked_sdk::witness::{MerkleAccumulator, SubmissionBuilder};
+    
+    fn builder() -> SubmissionBuilder {
+        SubmissionBuilder::from_secret_key(&[5u8; 32]).unwrap()
+    }
+    
+    #[test]
+    fn test_submission_hashes_and_root() {
+        let mut builder = builder();
+        let events: Vec<&[u8]> = vec![b"one", b"two", b"three"];
+        let mut leaves = Vec::new();
+        
+        for (sequence, event) in events.iter().enumerate() {
+            let submission = builder.build_at("af-1", event, 1_700_000_000);
+            let event_hash: [u8; 32] = Sha256::digest(event).into();
+            leaves.push(witness::leaf_hash(&event_hash));
+            
+            assert_eq!(submission.af_event_hash, hex::encode(event_hash));
+            assert_eq!(submission.af_merkle_root, hex::encode(tree_root(&leaves)));
+            assert_eq!(submission.af_sequence, sequence as u64);
+            assert_eq!(submission.af_instance_id, "af-1");
+            assert_eq!(submission.oracle_time, 1_700_000_000);
+        }
+    }
+    
+    #[test]
+    fn test_submission_sequences_are_per_instance() {
+        let mut builder = builder();
+        
+        assert_eq!(builder.build("af-1", b"a").af_sequence, 0);
+        assert_eq!(builder.build("af-1", b"b").af_sequence, 1);
+        assert_eq!(builder.build("af-2", b"c").af_sequence, 0);
+        assert_eq!(builder.next_sequence("af-1"), 2);
+        assert_eq!(builder.next_sequence("af-3"), 0);
+    }
+    
+    #[test]
+    fn test_submission_signature_verifies() {
+        let mut builder = builder();
+        let submission = builder.build("af-1", b"event");
+        
+        let signature_bytes = base64::engine::general_purpose::STANDARD
+            .decode(&submission.af_signature)
+            .unwrap();
+        let signature = Signature::from_slice(&signature_bytes).unwrap();
+        let payload = witness::submission_signing_payload(&submission);
+        
+        assert!(builder.verifying_key().verify(&payload, &signature).is_ok());
+        
+        let mut tampered = submission.clone();
+        tampered.af_sequence += 1;
+        let payload = witness::submission_signing_payload(&tampered);
+        assert!(builder.verifying_key().verify(&payload, &signature).is_err());
+    }
+    
+    #[test]
+    fn test_submission_resume_instance() {
+        let mut original = builder();
+        original.build("af-1", b"a");
+        original.build("af-1", b"b");
+        let expected = original.build("af-1", b"c");
+        
+        let mut tree = MerkleAccumulator::new();
+        for event in [b"a", b"b"] {
+            let hash: [u8; 32] = Sha256::digest(event).into();
+            tree.push(witness::leaf_hash(&hash));
+        }
+        let mut resumed = builder();
+        resumed.resume_instance("af-1", 2, tree);
+        let submission = resumed.build_at("af-1", b"c", expected.oracle_time);
+        
+        assert_eq!(submission.af_sequence, 2);
+        assert_eq!(submission.af_merkle_root, expected.af_merkle_root);
+        assert_eq!(submission.af_signature, expected.af_signature);
+    }
+    
+    #[test]
+    fn test_submission_builder_from_pem() {
+        let key = SigningKey::from_bytes(&[5u8; 32]);
+        let pem = key.to_pkcs8_pem(LineEnding::LF).unwrap();
+        
+        let builder = SubmissionBuilder::from_pkcs8_pem(&pem).unwrap();
+        
+        assert_eq!(builder.verifying_key(), key.verifying_key());
+        assert!(!format!("{:?}", builder).contains("05"));
+    }
+    
+    #[test]
+    fn test_submission_builder_invalid_key() {
+        assert!(matches!(
+            SubmissionBuilder::from_secret_key(&[1u8; 7]),
+            Err(TruthlinkedError::InvalidRequest(_))
+        ));
+        assert!(matches!(
+            SubmissionBuilder::from_pkcs8_pem("not a key"),
+            Err(TruthlinkedError::InvalidRequest(_))
+        ));
+    }
+}